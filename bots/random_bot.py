@@ -0,0 +1,16 @@
+#!/usr/bin/env python3
+"""A bot for `ExternalAgent`: reads one decision per line on stdin and answers
+with its `seq` and the index of a legal action, buying whatever it can afford.
+
+Use it from a roster entry with
+    {"name": "Py", "external": {"command": ["python3", "bots/random_bot.py"]}}
+"""
+import json
+import random
+import sys
+
+for line in sys.stdin:
+    request = json.loads(line)
+    actions = request["legal_actions"]
+    choice = actions.index("Buy") if "Buy" in actions else random.randrange(len(actions))
+    print(json.dumps({"seq": request["seq"], "action": choice}), flush=True)
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{player::PlayerId, square::BoardPosition};
+
+/// Properties and cash offered by one player in exchange for another's
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TradeOffer {
+    pub from: PlayerId,
+    pub to: PlayerId,
+    pub give: Vec<BoardPosition>,
+    pub take: Vec<BoardPosition>,
+    pub give_cash: usize,
+    pub take_cash: usize,
+}
+
+/// What the engine is waiting on before the game can carry on
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// Build, mortgage or trade before rolling
+    PreRoll,
+    Jail,
+    Buy {
+        position: BoardPosition,
+        price: usize,
+    },
+    Bid {
+        position: BoardPosition,
+        highest: usize,
+    },
+    Manage,
+    TradeResponse {
+        offer: TradeOffer,
+    },
+    /// Pay `flat` or `percentage`, the player's share of their assessed net worth
+    IncomeTax {
+        flat: usize,
+        percentage: usize,
+    },
+    /// The speed die showed the bus: move by one of the white dice or their total
+    Bus {
+        faces: Vec<usize>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PendingDecision {
+    pub player: PlayerId,
+    pub decision: Decision,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum Action {
+    Buy,
+    Decline,
+    Bid(usize),
+    Pass,
+    Build(BoardPosition),
+    /// Sell a house or hotel back to the bank for half of what it cost
+    SellBuilding(BoardPosition),
+    Mortgage(BoardPosition),
+    Unmortgage(BoardPosition),
+    ProposeTrade(TradeOffer),
+    EndTurn,
+    PayBail,
+    UseJailCard,
+    RollForDoubles,
+    AcceptTrade,
+    RejectTrade,
+    /// Done with pre-roll actions
+    Roll,
+    PayFlatTax,
+    PayTaxPercentage,
+    /// Squares to move after a bus roll
+    MoveBy(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IllegalAction {
+    pub player: PlayerId,
+    pub action: Action,
+}
+
+impl fmt::Display for IllegalAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Illegal action {:?} [Player={}]",
+            self.action, self.player
+        )
+    }
+}
+
+impl std::error::Error for IllegalAction {}
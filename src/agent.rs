@@ -1,7 +1,9 @@
-use crate::{player::Player, state::State};
+use crate::{
+    action::{Action, PendingDecision},
+    view::GameView,
+};
 
 pub trait Agent {
-    type SimState: State;
-
-    fn step(&self, state: &Self::SimState, players: Vec<&Player>);
+    /// Choose how to respond to the decision the engine is waiting on
+    fn decide(&mut self, view: &GameView<'_>, pending: &PendingDecision) -> Action;
 }
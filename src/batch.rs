@@ -0,0 +1,171 @@
+use serde::Serialize;
+
+use crate::{
+    agent::Agent,
+    config::BoardConfig,
+    game::Game,
+    monopoly::MonopolyState,
+    player::Player,
+    rules::Rules,
+    stats::BoardStats,
+    termination::{EndConditions, EndReason},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct BatchConfig {
+    pub games: usize,
+    /// Games still going after this many rounds are won on net worth
+    pub max_rounds: usize,
+    pub seed: u64,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            max_rounds: 500,
+            seed: 0,
+        }
+    }
+}
+
+/// Results per entrant, in the order the entrants were given
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BatchResult {
+    pub games: usize,
+    /// Every game has a winner, on net worth if it ran to the round limit
+    pub wins: Vec<usize>,
+    /// Games that hit the round limit with more than one player left
+    pub unfinished: usize,
+    /// 1 per outright win, plus the share of total net worth held in unfinished games
+    pub score: Vec<f64>,
+    pub mean_rounds: f64,
+    /// Landings, rent and investment per square over every game
+    pub board: BoardStats,
+}
+
+impl BatchResult {
+    pub fn mean_score(&self, entrant: usize) -> f64 {
+        self.score[entrant] / self.games.max(1) as f64
+    }
+}
+
+/// Play `config.games` games on `board` between `n_entrants` agents built by
+/// `make_agent(entrant, seed)`. Seats are rotated from game to game so nobody always goes first.
+pub fn run_batch<F>(
+    board: &BoardConfig,
+    n_entrants: usize,
+    config: BatchConfig,
+    mut make_agent: F,
+) -> BatchResult
+where
+    F: FnMut(usize, u64) -> Box<dyn Agent>,
+{
+    let mut result = BatchResult {
+        games: config.games,
+        wins: vec![0; n_entrants],
+        score: vec![0.0; n_entrants],
+        board: BoardStats::new(board.len()),
+        ..BatchResult::default()
+    };
+    let mut rounds = 0;
+
+    for g in 0..config.games {
+        let seed = config.seed.wrapping_add(g as u64);
+        // Seat `s` is taken by entrant `(s + g) % n_entrants`
+        let entrant = |seat: usize| (seat + g) % n_entrants;
+        let mut agents: Vec<_> = (0..n_entrants)
+            .map(|seat| {
+                make_agent(
+                    entrant(seat),
+                    seed.wrapping_mul(31).wrapping_add(seat as u64),
+                )
+            })
+            .collect();
+
+        let end = EndConditions::last_standing().with_max_rounds(config.max_rounds);
+        let state = MonopolyState::seeded(seed).with_rules(Rules::default().with_end(end));
+        let mut game = Game::with_board(Player::create_players(n_entrants), board, state);
+        game.record_events(true);
+        // Events are added up turn by turn rather than kept for the whole game
+        let mut tally = result.board.start_game(&game);
+        loop {
+            let playing = game.rounds() < config.max_rounds && game.play_turn(&mut agents);
+            let events = game.take_events();
+            result.board.add_events(&mut tally, &game, &events);
+            if !playing {
+                break;
+            }
+        }
+        let winner = game.winner();
+        rounds += game.rounds();
+        let winner = winner.and_then(|id| game.seat(id).ok());
+        if let Some(seat) = winner {
+            result.wins[entrant(seat)] += 1;
+        }
+
+        match (winner, game.end_reason()) {
+            (Some(seat), Some(EndReason::LastStanding)) => {
+                result.score[entrant(seat)] += 1.0;
+            }
+            _ => {
+                result.unfinished += 1;
+                let worth: Vec<f64> = game
+                    .players
+                    .iter()
+                    .map(|p| game.holdings(p).net_worth() as f64)
+                    .collect();
+                let total = worth.iter().sum::<f64>().max(1.0);
+                for (seat, w) in worth.iter().enumerate() {
+                    result.score[entrant(seat)] += w / total;
+                }
+            }
+        }
+    }
+
+    result.mean_rounds = rounds as f64 / config.games.max(1) as f64;
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{config::BoardConfig, strategy::Strategy};
+
+    use super::{run_batch, BatchConfig};
+
+    #[test]
+    fn every_game_is_accounted_for() {
+        let entrants = [Strategy::Greedy, Strategy::Random, Strategy::Cautious];
+        let config = BatchConfig {
+            games: 12,
+            max_rounds: 200,
+            seed: 3,
+        };
+        let result = run_batch(&BoardConfig::uk(), entrants.len(), config, |e, seed| {
+            entrants[e].agent(seed)
+        });
+
+        let wins: usize = result.wins.iter().sum();
+        assert_eq!(wins, 12);
+        assert!(result.unfinished <= 12);
+
+        let score: f64 = result.score.iter().sum();
+        assert!((score - 12.0).abs() < 1e-9);
+        assert_eq!(result.board.games, 12);
+        assert!(result.board.squares.iter().any(|s| s.rent > 0));
+    }
+
+    #[test]
+    fn games_are_played_on_the_given_board() {
+        let mut board = BoardConfig::uk();
+        board.squares.truncate(12);
+        let config = BatchConfig {
+            games: 2,
+            max_rounds: 20,
+            seed: 1,
+        };
+        let result = run_batch(&board, 2, config, |_, seed| Strategy::Greedy.agent(seed));
+        assert_eq!(result.board.squares.len(), 12);
+        assert!(result.board.squares.iter().all(|s| s.landings > 0));
+    }
+}
@@ -1,11 +1,244 @@
-use std::vec;
+use std::{error::Error, fs, path::Path};
 
-use monopoly_abm::monopoly::MonopolyState;
-use monopoly_abm::player::Player;
+use monopoly_abm::agent::Agent;
+use monopoly_abm::batch::{run_batch, BatchConfig};
+use monopoly_abm::config::BoardConfig;
+use monopoly_abm::game::Game;
+use monopoly_abm::html;
+use monopoly_abm::human::HumanAgent;
+use monopoly_abm::phase;
+use monopoly_abm::replay::Replay;
+use monopoly_abm::roster::{PlayerConfig, Roster};
+use monopoly_abm::stats::Metric;
+use monopoly_abm::strategy::Strategy;
+use monopoly_abm::svg;
+use monopoly_abm::termination::EndConditions;
+#[cfg(feature = "tui")]
+use monopoly_abm::tui::Viewer;
 
-fn main() {
-    let n_players: usize = 3;
-    let _players = Player::create_players(n_players);
-    let _game_state = MonopolyState::new(vec![]);
-    // players.iter().collect()
+/// Usage: `monopoly_abm [play] [--board uk|us|<board.json>] [--roster <roster.json>] [--dot]
+/// [--human [<seat>,...]] [--max-rounds <n>] [--stalemate <rounds>] [--first-bankruptcy]
+/// [--tui] [--record <replay.json>] [--replay <replay.json>] [--html <replay.html>] [--svg <board.svg>]
+/// [--heatmap <heatmap.svg> [--metric landings|rent|roi] [--games <n>]]`
+///
+/// `--dot` prints the turn diagram as Graphviz and exits. `--human` plays the given
+/// seats (seat 0 by default) from the terminal, bots taking the rest. Games stop after 1000 rounds
+/// unless `--max-rounds` says otherwise, the richest player winning. `--tui` watches the
+/// game full screen and `--replay` watches a recorded one, both need the `tui` feature.
+/// `--html` writes the game, or the `--replay` given, as a standalone page to share.
+/// `--svg` draws the board at the end of the game. `--heatmap` plays a batch of games
+/// between the roster's strategies and shades each square by the chosen statistic.
+fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|a| a == "play") {
+        args.remove(0);
+    }
+    check_args(&args)?;
+    if args.iter().any(|a| a == "--dot") {
+        print!("{}", phase::to_dot());
+        return Ok(());
+    }
+    let option = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1))
+    };
+
+    if let Some(path) = option("--replay") {
+        let replay = Replay::load(Path::new(path))?;
+        return match option("--html") {
+            Some(out) => Ok(fs::write(out, html::page(&replay)?)?),
+            None => watch_replay(replay),
+        };
+    }
+
+    let board = match option("--board") {
+        Some(name) => BoardConfig::load(name)?,
+        None => BoardConfig::uk(),
+    };
+    let roster = match option("--roster") {
+        Some(path) => Roster::load(Path::new(path))?,
+        None => Roster::new(vec![
+            PlayerConfig::new("Greedy", Strategy::Greedy),
+            PlayerConfig::new("Cautious", Strategy::Cautious),
+            PlayerConfig::new("Random", Strategy::Random),
+        ]),
+    };
+    let max_rounds = match option("--max-rounds") {
+        Some(n) => n.parse()?,
+        None => 1000,
+    };
+    let mut end = EndConditions::last_standing().with_max_rounds(max_rounds);
+    if let Some(rounds) = option("--stalemate") {
+        end = end.with_stalemate(rounds.parse()?);
+    }
+    if args.iter().any(|a| a == "--first-bankruptcy") {
+        end = end.with_first_bankruptcy();
+    }
+
+    if let Some(path) = option("--heatmap") {
+        let metric = match option("--metric") {
+            Some(name) => Metric::parse(name).ok_or_else(|| {
+                format!("Unknown metric '{}', expected landings, rent or roi", name)
+            })?,
+            None => Metric::Landings,
+        };
+        let games = match option("--games") {
+            Some(n) => n.parse()?,
+            None => 100,
+        };
+        let entrants = roster.seated()?;
+        let config = BatchConfig {
+            games,
+            max_rounds,
+            seed: rand::random(),
+        };
+        let result = run_batch(&board, entrants.len(), config, |e, seed| {
+            entrants[e].agent(seed).unwrap_or_else(|error| {
+                log::warn!("{}, playing {:?} instead", error, entrants[e].strategy);
+                entrants[e].strategy.agent(seed)
+            })
+        });
+        for (entrant, wins) in entrants.iter().zip(&result.wins) {
+            log::info!("{} won {} of {} games", entrant.name, wins, result.games);
+        }
+        let values = result.board.metric(metric);
+        fs::write(path, svg::heatmap(&board, &values, metric))?;
+        return Ok(());
+    }
+    log::info!("Playing on the {} board", board.name);
+
+    let (mut game, mut agents) = roster.game(&board, rand::random())?;
+    if let Some(i) = args.iter().position(|a| a == "--human") {
+        let seats: Vec<usize> = match args.get(i + 1).filter(|a| !a.starts_with("--")) {
+            Some(seats) => seats.split(',').map(str::parse).collect::<Result<_, _>>()?,
+            None => vec![0],
+        };
+        for seat in seats {
+            match agents.get_mut(seat) {
+                Some(agent) => *agent = Box::new(HumanAgent::stdio()),
+                None => return Err(format!("There is no seat {}", seat).into()),
+            }
+        }
+    }
+    game.state.rules.end = end;
+    let record = option("--record").map(Path::new);
+    if args.iter().any(|a| a == "--tui") {
+        if args.iter().any(|a| a == "--human") {
+            return Err("--human can't be combined with --tui".into());
+        }
+        return watch_live(game, agents, max_rounds, record);
+    }
+
+    let page = option("--html");
+    if record.is_some() || page.is_some() {
+        let replay = Replay::record(&mut game, &mut agents, max_rounds);
+        if let Some(path) = record {
+            replay.save(path)?;
+        }
+        if let Some(path) = page {
+            fs::write(path, html::page(&replay)?)?;
+        }
+    } else {
+        game.play(&mut agents, max_rounds);
+    }
+    if let Some(path) = option("--svg") {
+        fs::write(path, svg::snapshot(&game))?;
+    }
+    match game.winner() {
+        Some(winner) => log::info!(
+            "Winner {} after {} rounds ({:?}) [Player={}]",
+            game.player(winner)?.name,
+            game.rounds(),
+            game.end_reason(),
+            winner
+        ),
+        None => log::info!("No winner after {} rounds", game.rounds()),
+    }
+    for standing in game.leaderboard() {
+        log::info!(
+            "#{} {} net worth {} cash {} [Player={}]",
+            standing.rank,
+            standing.name,
+            standing.net_worth,
+            standing.cash,
+            standing.player
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "tui")]
+fn watch_live(
+    game: Game,
+    agents: Vec<Box<dyn Agent>>,
+    max_rounds: usize,
+    record: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let mut viewer = Viewer::live(game, agents, max_rounds);
+    viewer.run()?;
+    if let Some(path) = record {
+        viewer.into_replay().save(path)?;
+    }
+    Ok(())
+}
+
+/// Flags followed by a value.
+const OPTIONS: &[&str] = &[
+    "--board",
+    "--roster",
+    "--max-rounds",
+    "--stalemate",
+    "--record",
+    "--replay",
+    "--html",
+    "--svg",
+    "--heatmap",
+    "--metric",
+    "--games",
+];
+
+/// Flags standing alone.
+const SWITCHES: &[&str] = &["--dot", "--first-bankruptcy", "--tui"];
+
+/// Rejects anything the usage doesn't list, so a mistyped flag isn't silently ignored.
+fn check_args(args: &[String]) -> Result<(), String> {
+    let mut rest = args.iter().peekable();
+    while let Some(arg) = rest.next() {
+        if OPTIONS.contains(&arg.as_str()) {
+            if rest.next().is_none() {
+                return Err(format!("{} needs a value", arg));
+            }
+        } else if arg == "--human" {
+            rest.next_if(|seats| !seats.starts_with("--"));
+        } else if !SWITCHES.contains(&arg.as_str()) {
+            return Err(format!("Unknown argument '{}'", arg));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "tui")]
+fn watch_replay(replay: Replay) -> Result<(), Box<dyn Error>> {
+    Ok(Viewer::replay(replay).run()?)
+}
+
+#[cfg(not(feature = "tui"))]
+const NO_TUI: &str = "Built without the terminal viewer, rebuild with `--features tui`";
+
+#[cfg(not(feature = "tui"))]
+fn watch_live(
+    _game: Game,
+    _agents: Vec<Box<dyn Agent>>,
+    _max_rounds: usize,
+    _record: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    Err(NO_TUI.into())
+}
+
+#[cfg(not(feature = "tui"))]
+fn watch_replay(_replay: Replay) -> Result<(), Box<dyn Error>> {
+    Err(NO_TUI.into())
 }
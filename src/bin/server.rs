@@ -0,0 +1,79 @@
+use std::{error::Error, time::Duration};
+
+use monopoly_abm::config::BoardConfig;
+use monopoly_abm::server::{Server, ServerConfig};
+use monopoly_abm::strategy::Strategy;
+use monopoly_abm::termination::EndConditions;
+
+/// Usage: `server [--port <port>] [--players <n>] [--bots Greedy,Cautious,...]
+/// [--board uk|us|<board.json>] [--max-rounds <n>] [--timeout <seconds>]`
+///
+/// Hosts one game on the LAN. `--players` seats (2 by default) are taken by clients
+/// sending `{"Join": {"name": ..., "seat": null}}` as a JSON line, the `--bots` sit after
+/// them. Anyone else can `"Spectate"`. A player who doesn't answer within `--timeout`
+/// is played for by the Greedy strategy, without waiting, until they send something
+/// again or `Rejoin`.
+fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let option = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1))
+    };
+
+    let port: u16 = match option("--port") {
+        Some(port) => port.parse()?,
+        None => 4000,
+    };
+    let players: usize = match option("--players") {
+        Some(n) => n.parse()?,
+        None => 2,
+    };
+    let mut seats = vec![None; players];
+    if let Some(bots) = option("--bots") {
+        for name in bots.split(',') {
+            let bot: Strategy = serde_json::from_value(serde_json::json!(name))
+                .map_err(|_| format!("Unknown strategy '{}'", name))?;
+            seats.push(Some(bot));
+        }
+    }
+    let board = match option("--board") {
+        Some(name) => BoardConfig::load(name)?,
+        None => BoardConfig::uk(),
+    };
+    let max_rounds = match option("--max-rounds") {
+        Some(n) => n.parse()?,
+        None => 1000,
+    };
+    let timeout = match option("--timeout") {
+        Some(seconds) => Duration::from_secs(seconds.parse()?),
+        None => Duration::from_secs(60),
+    };
+
+    let config = ServerConfig::new(seats)
+        .with_board(board)
+        .with_seed(rand::random())
+        .with_end(EndConditions::last_standing().with_max_rounds(max_rounds))
+        .with_timeout(timeout);
+    let server = Server::bind(("0.0.0.0", port), config)?;
+    log::info!(
+        "Waiting for {} players on {}",
+        players,
+        server.local_addr()?
+    );
+
+    let game = server.run()?;
+    for standing in game.leaderboard() {
+        log::info!(
+            "#{} {} net worth {} cash {} [Player={}]",
+            standing.rank,
+            standing.name,
+            standing.net_worth,
+            standing.cash,
+            standing.player
+        );
+    }
+    Ok(())
+}
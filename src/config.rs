@@ -1,14 +1,37 @@
-use serde::Deserialize;
+use std::{collections::HashMap, fs, path::Path};
 
-use crate::locations::BoardLocation;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+use crate::error::MonopolyError;
+use crate::locations::{BoardLocation, ColourGroup, SquareKind};
+use crate::square::BoardPosition;
+
+/// Everything about a square that is fixed by the edition being played
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Property {
-    pub location: BoardLocation,
+    pub name: String,
+    pub kind: SquareKind,
+    #[serde(default)]
+    pub group: Option<ColourGroup>,
+    #[serde(default)]
     pub tile_cost: usize,
-    pub charge: usize,
+    /// Rent unimproved, with 1 to 4 houses and with a hotel. Stations list the rent
+    /// with 1 to 4 stations owned and utilities the dice multiplier with 1 or 2.
+    #[serde(default)]
+    pub rent: [usize; 6],
+    /// Paid out by the bank when the square is mortgaged
+    #[serde(default)]
+    pub mortgage: usize,
+    #[serde(default)]
     pub house_cost: usize,
+    #[serde(default)]
     pub hotel_cost: usize,
+    /// Charged on `SquareKind::Tax` squares
+    #[serde(default)]
+    pub tax: usize,
+    /// Percentage of net worth that may be paid instead of `tax`, 0 when there's no choice
+    #[serde(default)]
+    pub tax_rate: usize,
 }
 
 impl Property {
@@ -16,16 +39,337 @@ impl Property {
     pub fn new(
         location: BoardLocation,
         tile_cost: usize,
-        charge: usize,
+        rent: [usize; 6],
         house_cost: usize,
         hotel_cost: usize,
     ) -> Self {
         Self {
-            location,
+            name: location.name().to_string(),
+            kind: location.kind(),
+            group: location.colour_group(),
             tile_cost,
-            charge,
+            rent,
+            mortgage: tile_cost / 2,
             house_cost,
             hotel_cost,
+            tax: 0,
+            tax_rate: 0,
+        }
+    }
+
+    pub const fn with_mortgage(mut self, mortgage: usize) -> Self {
+        self.mortgage = mortgage;
+        self
+    }
+
+    /// Rent with `level` houses, a hotel being level 5
+    pub fn rent_at(&self, level: usize) -> usize {
+        self.rent[level.min(5)]
+    }
+
+    pub const fn with_tax(mut self, tax: usize) -> Self {
+        self.tax = tax;
+        self
+    }
+
+    pub const fn with_tax_rate(mut self, percent: usize) -> Self {
+        self.tax_rate = percent;
+        self
+    }
+}
+
+/// Official price, rent table, building cost and mortgage value for every square on the UK board
+pub fn default_config() -> HashMap<BoardLocation, Property> {
+    let mut config = HashMap::new();
+    for location in BoardLocation::all() {
+        let (tile_cost, rent, house_cost) = match location {
+            BoardLocation::OldKentRoad => (60, [2, 10, 30, 90, 160, 250], 50),
+            BoardLocation::WhitechapelRoad => (60, [4, 20, 60, 180, 320, 450], 50),
+            BoardLocation::TheAngelIslington | BoardLocation::EustonRoad => {
+                (100, [6, 30, 90, 270, 400, 550], 50)
+            }
+            BoardLocation::PentonvilleRoad => (120, [8, 40, 100, 300, 450, 600], 50),
+            BoardLocation::PallMall | BoardLocation::Whitehall => {
+                (140, [10, 50, 150, 450, 625, 750], 100)
+            }
+            BoardLocation::NorthumberlandAvenue => (160, [12, 60, 180, 500, 700, 900], 100),
+            BoardLocation::BowStreet | BoardLocation::GreatMarlboroughStreet => {
+                (180, [14, 70, 200, 550, 750, 950], 100)
+            }
+            BoardLocation::VineStreet => (200, [16, 80, 220, 600, 800, 1000], 100),
+            BoardLocation::TheStrand | BoardLocation::FleetStreet => {
+                (220, [18, 90, 250, 700, 875, 1050], 150)
+            }
+            BoardLocation::TrafalgarSquare => (240, [20, 100, 300, 750, 925, 1100], 150),
+            BoardLocation::LeicesterSquare | BoardLocation::CoventryStreet => {
+                (260, [22, 110, 330, 800, 975, 1150], 150)
+            }
+            BoardLocation::Piccadilly => (280, [24, 120, 360, 850, 1025, 1200], 150),
+            BoardLocation::RegentStreet | BoardLocation::OxfordStreet => {
+                (300, [26, 130, 390, 900, 1100, 1275], 200)
+            }
+            BoardLocation::BondStreet => (320, [28, 150, 450, 1000, 1200, 1400], 200),
+            BoardLocation::ParkLane => (350, [35, 175, 500, 1100, 1300, 1500], 200),
+            BoardLocation::Mayfair => (400, [50, 200, 600, 1400, 1700, 2000], 200),
+            BoardLocation::MaryleboneStation
+            | BoardLocation::FenchurchStreetStation
+            | BoardLocation::KingCrossStation
+            | BoardLocation::LiverpoolStreetStation => (200, [25, 50, 100, 200, 0, 0], 0),
+            BoardLocation::ElectricCompany | BoardLocation::WaterCompany => {
+                (150, [4, 10, 0, 0, 0, 0], 0)
+            }
+            _ => (0, [0; 6], 0),
+        };
+        let (tax, tax_rate) = match location {
+            BoardLocation::IncomeTax => (200, 10),
+            BoardLocation::LuxuryTax => (100, 0),
+            _ => (0, 0),
+        };
+        config.insert(
+            location,
+            Property::new(location, tile_cost, rent, house_cost, house_cost)
+                .with_tax(tax)
+                .with_tax_rate(tax_rate),
+        );
+    }
+
+    config
+}
+
+/// Square names of the US (Atlantic City) edition, in board order
+const US_NAMES: [&str; 40] = [
+    "Go",
+    "Mediterranean Avenue",
+    "Community Chest",
+    "Baltic Avenue",
+    "Income Tax",
+    "Reading Railroad",
+    "Oriental Avenue",
+    "Chance",
+    "Vermont Avenue",
+    "Connecticut Avenue",
+    "Jail",
+    "St. Charles Place",
+    "Electric Company",
+    "States Avenue",
+    "Virginia Avenue",
+    "Pennsylvania Railroad",
+    "St. James Place",
+    "Community Chest",
+    "Tennessee Avenue",
+    "New York Avenue",
+    "Free Parking",
+    "Kentucky Avenue",
+    "Chance",
+    "Indiana Avenue",
+    "Illinois Avenue",
+    "B. & O. Railroad",
+    "Atlantic Avenue",
+    "Ventnor Avenue",
+    "Water Works",
+    "Marvin Gardens",
+    "Go To Jail",
+    "Pacific Avenue",
+    "North Carolina Avenue",
+    "Community Chest",
+    "Pennsylvania Avenue",
+    "Short Line",
+    "Chance",
+    "Park Place",
+    "Luxury Tax",
+    "Boardwalk",
+];
+
+/// A complete board, one entry per square starting from Go
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BoardConfig {
+    pub name: String,
+    pub squares: Vec<Property>,
+    /// Names of the squares the "advance to" Chance cards send players to, in card order.
+    /// A card whose square isn't on the board is put back without moving anyone.
+    #[serde(default)]
+    pub chance_destinations: Vec<String>,
+}
+
+impl BoardConfig {
+    pub fn uk() -> Self {
+        let config = default_config();
+        Self {
+            name: "UK".to_string(),
+            squares: BoardLocation::all()
+                .map(|location| config[&location].clone())
+                .collect(),
+            chance_destinations: [
+                BoardLocation::PallMall,
+                BoardLocation::MaryleboneStation,
+                BoardLocation::TrafalgarSquare,
+                BoardLocation::Mayfair,
+            ]
+            .map(|location| location.name().to_string())
+            .to_vec(),
+        }
+    }
+
+    /// The Atlantic City board, laid out and priced like the UK one
+    pub fn us() -> Self {
+        let mut board = Self::uk();
+        board.name = "US".to_string();
+        for (square, name) in board.squares.iter_mut().zip(US_NAMES.iter()) {
+            square.name = name.to_string();
+            if square.kind == SquareKind::Tax && square.tax == 100 {
+                square.tax = 75;
+            }
+        }
+        board.chance_destinations = [
+            "St. Charles Place",
+            "Reading Railroad",
+            "Illinois Avenue",
+            "Boardwalk",
+        ]
+        .map(String::from)
+        .to_vec();
+        board
+    }
+
+    /// A built-in board by name, `uk` or `us`
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "uk" | "london" => Some(Self::uk()),
+            "us" | "atlantic-city" => Some(Self::us()),
+            _ => None,
+        }
+    }
+
+    /// A built-in board name, or the path of a JSON board file
+    pub fn load(name_or_path: &str) -> Result<Self, MonopolyError> {
+        match Self::preset(name_or_path) {
+            Some(board) => Ok(board),
+            None => Self::from_json(&fs::read_to_string(Path::new(name_or_path))?),
         }
     }
+
+    pub fn from_json(json: &str) -> Result<Self, MonopolyError> {
+        let board: Self = serde_json::from_str(json)?;
+        board.validate()?;
+        Ok(board)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), MonopolyError> {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    pub fn len(&self) -> usize {
+        self.squares.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.squares.is_empty()
+    }
+
+    /// Where each of the `chance_destinations` is, `None` for a square not on the board
+    pub fn chance_positions(&self) -> Vec<Option<BoardPosition>> {
+        self.chance_destinations
+            .iter()
+            .map(|name| self.squares.iter().position(|sq| &sq.name == name))
+            .collect()
+    }
+
+    /// Go must come first and there must be a jail to send players to
+    fn validate(&self) -> Result<(), MonopolyError> {
+        let invalid = |reason: &str| {
+            Err(MonopolyError::InvalidBoard(format!(
+                "Board '{}' {}",
+                self.name, reason
+            )))
+        };
+
+        match self.squares.first() {
+            None => return invalid("has no squares"),
+            Some(go) if go.kind != SquareKind::Go => return invalid("does not start at Go"),
+            _ => {}
+        }
+        if !self.squares.iter().any(|sq| sq.kind == SquareKind::Jail) {
+            return invalid("has no jail");
+        }
+        if let Some(sq) = self
+            .squares
+            .iter()
+            .find(|sq| sq.kind.is_ownable() && sq.group.is_none())
+        {
+            return invalid(&format!("has '{}' without a colour group", sq.name));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::locations::{BoardLocation, SquareKind};
+
+    use super::{default_config, BoardConfig};
+
+    #[test]
+    fn presets_share_a_layout() {
+        let uk = BoardConfig::uk();
+        let us = BoardConfig::us();
+        assert_eq!(uk.len(), 40);
+        assert_eq!(us.len(), 40);
+        assert_eq!(uk.squares[39].name, "Mayfair");
+        assert_eq!(us.squares[39].name, "Boardwalk");
+        for (a, b) in uk.squares.iter().zip(&us.squares) {
+            assert_eq!(
+                (a.kind, a.group, a.tile_cost),
+                (b.kind, b.group, b.tile_cost)
+            );
+        }
+        assert_eq!(BoardConfig::preset("US"), Some(us));
+    }
+
+    #[test]
+    fn chance_destinations_are_found_by_name() {
+        assert_eq!(
+            BoardConfig::uk().chance_positions(),
+            [Some(11), Some(15), Some(24), Some(39)]
+        );
+        assert_eq!(BoardConfig::us().chance_positions()[1], Some(5));
+
+        let mut board = BoardConfig::uk();
+        board.squares.truncate(20);
+        assert_eq!(board.chance_positions(), [Some(11), Some(15), None, None]);
+    }
+
+    #[test]
+    fn custom_boards_load_from_json() {
+        let mut board = BoardConfig::uk();
+        board.name = "Short".to_string();
+        board.squares.truncate(12);
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(BoardConfig::from_json(&json).unwrap(), board);
+
+        board.squares.retain(|sq| sq.kind != SquareKind::Jail);
+        let json = serde_json::to_string(&board).unwrap();
+        assert!(BoardConfig::from_json(&json).is_err());
+
+        let minimal = r#"{"name": "Tiny", "squares": [
+            {"name": "Go", "kind": "Go"},
+            {"name": "Lane", "kind": "Street", "group": "Brown", "tile_cost": 60,
+             "rent": [2, 10, 30, 90, 160, 250], "mortgage": 30},
+            {"name": "Jail", "kind": "Jail"}
+        ]}"#;
+        let lane = &BoardConfig::from_json(minimal).unwrap().squares[1];
+        assert_eq!((lane.house_cost, lane.mortgage), (0, 30));
+        assert_eq!(lane.rent_at(5), 250);
+    }
+
+    #[test]
+    fn built_in_board_has_official_values() {
+        let config = default_config();
+        let mayfair = &config[&BoardLocation::Mayfair];
+        assert_eq!(mayfair.rent, [50, 200, 600, 1400, 1700, 2000]);
+        assert_eq!((mayfair.tile_cost, mayfair.mortgage), (400, 200));
+        assert_eq!(config[&BoardLocation::OldKentRoad].rent_at(3), 90);
+        assert_eq!(config[&BoardLocation::KingCrossStation].mortgage, 100);
+        assert_eq!(config[&BoardLocation::KingCrossStation].rent_at(3), 200);
+        assert_eq!(config[&BoardLocation::WaterCompany].rent_at(1), 10);
+    }
 }
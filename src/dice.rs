@@ -1,60 +1,229 @@
-use std::ops::Add;
+use std::collections::VecDeque;
+use std::fmt;
 
-use rand::distributions::{Distribution, Uniform};
-use rand::prelude::ThreadRng;
+use rand::{Rng, RngCore};
 
+/// Faces of the Mega Edition speed die other than 1, 2 and 3
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeedFace {
+    Number(usize),
+    /// Once the move is over, carry on to the next unowned property
+    MrMonopoly,
+    /// Move by either white die or both, the player's choice
+    Bus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RollState {
+    /// Face of every numbered die thrown, the speed die excluded
+    pub faces: Vec<usize>,
+    pub speed: Option<SpeedFace>,
+    /// Squares to move
     pub value: usize,
     pub is_double: bool,
 }
 
-impl Default for RollState {
-    fn default() -> Self {
-        RollState {
-            value: 0,
-            is_double: true,
+impl RollState {
+    /// A doubles roll needs at least two dice all showing the same face
+    pub fn from_faces(faces: &[usize]) -> Self {
+        Self {
+            faces: faces.to_vec(),
+            speed: None,
+            value: faces.iter().sum(),
+            is_double: faces.len() > 1 && faces.iter().all(|&f| f == faces[0]),
         }
     }
+
+    pub fn with_speed(mut self, speed: SpeedFace) -> Self {
+        if let SpeedFace::Number(n) = speed {
+            self.value += n;
+        }
+        self.speed = Some(speed);
+        self
+    }
+}
+
+/// Where the engine gets its rolls from. `rng` is the game's own generator so
+/// seeded games stay reproducible.
+pub trait Dice: fmt::Debug {
+    fn roll(&mut self, rng: &mut dyn RngCore) -> RollState;
+
+    /// `Game` is cloned for look-ahead search, so the dice have to be too
+    fn box_clone(&self) -> Box<dyn Dice>;
+
+    /// The same dice with any rolls known in advance forgotten, for search copies
+    fn unscripted(&self) -> Box<dyn Dice> {
+        self.box_clone()
+    }
+}
+
+impl Clone for Box<dyn Dice> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// `count` dice with `faces` sides each
+#[derive(Debug, Clone, Copy)]
+pub struct NDice {
+    pub count: usize,
+    pub faces: usize,
+}
+
+impl NDice {
+    pub const fn new(count: usize, faces: usize) -> Self {
+        Self { count, faces }
+    }
+
+    /// The usual pair of six sided dice
+    pub const fn standard() -> Self {
+        Self::new(2, 6)
+    }
+
+    fn faces(&self, rng: &mut dyn RngCore) -> Vec<usize> {
+        (0..self.count)
+            .map(|_| rng.gen_range(1..=self.faces.max(1)))
+            .collect()
+    }
+}
+
+impl Default for NDice {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl Dice for NDice {
+    fn roll(&mut self, rng: &mut dyn RngCore) -> RollState {
+        RollState::from_faces(&self.faces(rng))
+    }
+
+    fn box_clone(&self) -> Box<dyn Dice> {
+        Box::new(*self)
+    }
 }
 
-impl Add for RollState {
-    type Output = Self;
+/// Two white dice plus the speed die: 1, 2, 3, Mr. Monopoly twice and the bus
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpeedDice;
 
-    fn add(self, other: Self) -> Self {
+impl SpeedDice {
+    const FACES: [SpeedFace; 6] = [
+        SpeedFace::Number(1),
+        SpeedFace::Number(2),
+        SpeedFace::Number(3),
+        SpeedFace::MrMonopoly,
+        SpeedFace::MrMonopoly,
+        SpeedFace::Bus,
+    ];
+}
+
+impl Dice for SpeedDice {
+    fn roll(&mut self, rng: &mut dyn RngCore) -> RollState {
+        let white = NDice::standard().faces(rng);
+        let speed = Self::FACES[rng.gen_range(0..Self::FACES.len())];
+        RollState::from_faces(&white).with_speed(speed)
+    }
+
+    fn box_clone(&self) -> Box<dyn Dice> {
+        Box::new(*self)
+    }
+}
+
+/// Hands out the queued rolls in order, then falls back to rolling for real
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedDice {
+    rolls: VecDeque<RollState>,
+    fallback: NDice,
+}
+
+impl ScriptedDice {
+    pub fn new(rolls: Vec<RollState>) -> Self {
         Self {
-            value: self.value + other.value,
-            is_double: self.value.eq(&other.value),
+            rolls: rolls.into(),
+            fallback: NDice::standard(),
         }
     }
-}
 
-pub fn roll_game_dice(rng: &mut ThreadRng) -> RollState {
-    let distribution = Uniform::new_inclusive(1, 6);
-    (0..2).fold(RollState::default(), |a, r| {
-        let new = RollState {
-            value: distribution.sample(rng),
-            is_double: true,
-        };
+    /// Each entry is the faces of one roll, e.g. `&[&[3, 3], &[1, 2]]`
+    pub fn from_faces(rolls: &[&[usize]]) -> Self {
+        Self::new(rolls.iter().map(|f| RollState::from_faces(f)).collect())
+    }
+
+    pub fn push(&mut self, roll: RollState) {
+        self.rolls.push_back(roll);
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.rolls.len()
+    }
+}
 
-        if r == 0 {
-            new
-        } else {
-            new.add(a)
+impl Dice for ScriptedDice {
+    fn roll(&mut self, rng: &mut dyn RngCore) -> RollState {
+        match self.rolls.pop_front() {
+            Some(roll) => roll,
+            None => self.fallback.roll(rng),
         }
-    })
+    }
+
+    fn box_clone(&self) -> Box<dyn Dice> {
+        Box::new(self.clone())
+    }
+
+    fn unscripted(&self) -> Box<dyn Dice> {
+        Box::new(self.fallback)
+    }
+}
+
+pub fn roll_game_dice(rng: &mut impl Rng) -> RollState {
+    NDice::standard().roll(rng)
 }
 
 #[cfg(test)]
 mod test {
-    use super::roll_game_dice;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{roll_game_dice, Dice, NDice, RollState, ScriptedDice, SpeedDice, SpeedFace};
 
     #[test]
     fn test_check_rolling_works() {
-        let mut r = rand::thread_rng();
+        let mut r = StdRng::seed_from_u64(7);
 
         for _ in 0..=1000 {
             let the_roll = roll_game_dice(&mut r);
             assert!(the_roll.value <= 12 && the_roll.value > 1);
+            assert_eq!(the_roll.faces.len(), 2);
+            assert_eq!(the_roll.is_double, the_roll.faces[0] == the_roll.faces[1]);
         }
     }
+
+    #[test]
+    fn dice_models() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let mut d20 = NDice::new(3, 20);
+        for _ in 0..100 {
+            let roll = d20.roll(&mut rng);
+            assert_eq!(roll.faces.len(), 3);
+            assert!((3..=60).contains(&roll.value));
+        }
+
+        let mut speed = SpeedDice;
+        for _ in 0..100 {
+            let roll = speed.roll(&mut rng);
+            let white: usize = roll.faces.iter().sum();
+            match roll.speed {
+                Some(SpeedFace::Number(n)) => assert_eq!(roll.value, white + n),
+                Some(_) => assert_eq!(roll.value, white),
+                None => panic!("the speed die was not rolled"),
+            }
+        }
+
+        let mut scripted = ScriptedDice::from_faces(&[&[3, 3], &[1, 2]]);
+        assert_eq!(scripted.roll(&mut rng), RollState::from_faces(&[3, 3]));
+        assert!(!scripted.roll(&mut rng).is_double);
+        assert_eq!(scripted.remaining(), 0);
+        assert_eq!(scripted.roll(&mut rng).faces.len(), 2);
+    }
 }
@@ -0,0 +1,138 @@
+use std::{fmt, io};
+
+use crate::{
+    action::IllegalAction, locations::BoardLocation, phase::InvalidTransition, player::PlayerId,
+    roster::RosterError, square::BoardPosition,
+};
+
+/// Everything that can go wrong setting up or playing a game
+#[derive(Debug)]
+pub enum MonopolyError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// A board that can't be played on, with the reason
+    InvalidBoard(String),
+    /// The square is missing from the board config it was built from
+    MissingSquare(BoardLocation),
+    Roster(RosterError),
+    /// A scenario that can't be set up, with the reason
+    InvalidScenario(String),
+    /// A setting that is out of range, with the reason
+    InvalidConfig(String),
+    /// Every expectation the scenario didn't meet
+    ScenarioFailed(Vec<String>),
+    InsufficientFunds {
+        player: PlayerId,
+        amount: usize,
+        balance: usize,
+    },
+    CannotBuild(BoardPosition),
+    /// Not the player's, already mortgaged or still has buildings on it
+    CannotMortgage(BoardPosition),
+    IllegalAction(IllegalAction),
+    UnknownPlayer(PlayerId),
+    InvalidTransition(InvalidTransition),
+}
+
+impl fmt::Display for MonopolyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Json(e) => write!(f, "Invalid JSON: {}", e),
+            Self::InvalidBoard(reason)
+            | Self::InvalidScenario(reason)
+            | Self::InvalidConfig(reason) => write!(f, "{}", reason),
+            Self::MissingSquare(location) => {
+                write!(f, "{:?} is missing from the board config", location)
+            }
+            Self::Roster(e) => write!(f, "{}", e),
+            Self::ScenarioFailed(failures) => write!(f, "{}", failures.join("\n")),
+            Self::InsufficientFunds {
+                player,
+                amount,
+                balance,
+            } => write!(
+                f,
+                "Can't pay {} with a balance of {} [Player={}]",
+                amount, balance, player
+            ),
+            Self::CannotBuild(position) => write!(f, "Nothing more can be built on {}", position),
+            Self::CannotMortgage(position) => write!(f, "{} can't be mortgaged", position),
+            Self::IllegalAction(e) => write!(f, "{}", e),
+            Self::UnknownPlayer(id) => write!(f, "Player {} isn't playing", id),
+            Self::InvalidTransition(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for MonopolyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Json(e) => Some(e),
+            Self::Roster(e) => Some(e),
+            Self::IllegalAction(e) => Some(e),
+            Self::InvalidTransition(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MonopolyError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for MonopolyError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+impl From<RosterError> for MonopolyError {
+    fn from(e: RosterError) -> Self {
+        Self::Roster(e)
+    }
+}
+
+impl From<IllegalAction> for MonopolyError {
+    fn from(e: IllegalAction) -> Self {
+        Self::IllegalAction(e)
+    }
+}
+
+impl From<InvalidTransition> for MonopolyError {
+    fn from(e: InvalidTransition) -> Self {
+        Self::InvalidTransition(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{action::Action, config::BoardConfig, game::Game};
+
+    use super::MonopolyError;
+
+    #[test]
+    fn errors_are_returned_not_panicked() {
+        assert!(matches!(
+            BoardConfig::from_json("{"),
+            Err(MonopolyError::Json(_))
+        ));
+
+        let mut game = Game::new(2, 1);
+        assert!(matches!(
+            game.apply(Action::Buy),
+            Err(MonopolyError::IllegalAction(_))
+        ));
+
+        let player = &game.players[0];
+        let balance = player.current_balance();
+        assert!(matches!(
+            player.pay(balance + 1),
+            Err(MonopolyError::InsufficientFunds { .. })
+        ));
+        assert_eq!(player.current_balance(), balance);
+    }
+}
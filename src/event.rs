@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{action::TradeOffer, locations::SquareKind, player::PlayerId, square::BoardPosition};
+
+/// Something that happened in the game, recorded when `Game::record_events` is on
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum Event {
+    Rolled {
+        player: PlayerId,
+        faces: Vec<usize>,
+        value: usize,
+    },
+    Moved {
+        player: PlayerId,
+        from: BoardPosition,
+        to: BoardPosition,
+    },
+    PassedGo {
+        player: PlayerId,
+    },
+    Card {
+        player: PlayerId,
+        kind: SquareKind,
+        card: usize,
+    },
+    Rent {
+        player: PlayerId,
+        owner: PlayerId,
+        amount: usize,
+    },
+    Bought {
+        player: PlayerId,
+        position: BoardPosition,
+        price: usize,
+    },
+    Built {
+        player: PlayerId,
+        position: BoardPosition,
+    },
+    SoldBuilding {
+        player: PlayerId,
+        position: BoardPosition,
+        refund: usize,
+    },
+    Mortgaged {
+        player: PlayerId,
+        position: BoardPosition,
+    },
+    Unmortgaged {
+        player: PlayerId,
+        position: BoardPosition,
+    },
+    Traded {
+        offer: TradeOffer,
+    },
+    Jailed {
+        player: PlayerId,
+    },
+    Released {
+        player: PlayerId,
+    },
+    Bankrupt {
+        player: PlayerId,
+        creditor: Option<PlayerId>,
+    },
+    GameOver {
+        winner: Option<PlayerId>,
+    },
+}
@@ -0,0 +1,389 @@
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action::{Action, Decision, PendingDecision},
+    agent::Agent,
+    player::PlayerId,
+    view::{GameView, PlayerView, SquareView},
+};
+
+/// How long a bot gets to answer unless its config says otherwise
+pub const DEFAULT_TIMEOUT_MS: u64 = 1000;
+/// How long a bot gets to exit by itself once its stdin is closed
+const EXIT_GRACE: Duration = Duration::from_millis(200);
+
+const fn default_timeout() -> u64 {
+    DEFAULT_TIMEOUT_MS
+}
+
+/// A bot that runs as its own process, in any language
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct ExternalConfig {
+    /// The program followed by its arguments
+    pub command: Vec<String>,
+    #[serde(default = "default_timeout")]
+    pub timeout_ms: u64,
+}
+
+impl ExternalConfig {
+    pub fn new(command: &[&str]) -> Self {
+        Self {
+            command: command.iter().map(ToString::to_string).collect(),
+            timeout_ms: DEFAULT_TIMEOUT_MS,
+        }
+    }
+
+    pub const fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+}
+
+/// The line written to the bot for every decision
+#[derive(Debug, Serialize)]
+pub struct Request<'a> {
+    /// Echoed in the `Answer` so a late one can't be taken for the next decision
+    pub seq: u64,
+    pub player: PlayerId,
+    pub decision: &'a Decision,
+    pub round: usize,
+    pub free_parking: usize,
+    pub players: Vec<PlayerView<'a>>,
+    pub squares: Vec<SquareView<'a>>,
+    /// The first is the do-nothing choice
+    pub legal_actions: Vec<Action>,
+    /// Lowest and highest bid allowed while bidding
+    pub bid_range: Option<(usize, usize)>,
+}
+
+impl<'a> Request<'a> {
+    pub fn new(view: &GameView<'a>, pending: &'a PendingDecision) -> Self {
+        Self {
+            seq: 0,
+            player: pending.player,
+            decision: &pending.decision,
+            round: view.rounds(),
+            free_parking: view.free_parking(),
+            players: view.players().collect(),
+            squares: view.squares().collect(),
+            legal_actions: view.legal_actions(),
+            bid_range: view.bid_range().map(|r| (*r.start(), *r.end())),
+        }
+    }
+
+    pub const fn with_seq(mut self, seq: u64) -> Self {
+        self.seq = seq;
+        self
+    }
+}
+
+/// The choice made: an index into `legal_actions`, or any legal action in full
+/// such as `"Roll"` or `{"Bid": 120}`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Reply {
+    Index(usize),
+    Action(Action),
+}
+
+impl Reply {
+    /// `None` when the index is past the end of `legal_actions`
+    pub fn action(self, legal_actions: &[Action]) -> Option<Action> {
+        match self {
+            Self::Index(i) => legal_actions.get(i).cloned(),
+            Self::Action(action) => Some(action),
+        }
+    }
+}
+
+/// The line read back, e.g. `{"seq": 7, "action": 0}`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Answer {
+    /// The `seq` of the request answered
+    pub seq: u64,
+    pub action: Reply,
+}
+
+#[derive(Debug)]
+enum BotError {
+    Exited,
+    Timeout(Duration),
+    /// Still hasn't read the previous request
+    NotReading,
+    Unreadable(String),
+    NoSuchIndex(usize),
+    Illegal(Action),
+}
+
+impl fmt::Display for BotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exited => write!(f, "Bot has exited"),
+            Self::Timeout(timeout) => write!(f, "Bot didn't answer within {:?}", timeout),
+            Self::NotReading => write!(f, "Bot isn't reading its requests"),
+            Self::Unreadable(line) => write!(f, "Bot answered '{}'", line),
+            Self::NoSuchIndex(i) => write!(f, "Bot picked action {} which isn't listed", i),
+            Self::Illegal(action) => write!(f, "Bot picked illegal action {:?}", action),
+        }
+    }
+}
+
+/// Asks a child process for every decision as one JSON line in and one out.
+/// Slow, unreadable or illegal answers are settled by the fallback agent, which
+/// plays on by itself once the process has gone.
+pub struct ExternalAgent {
+    child: Child,
+    requests: Option<SyncSender<String>>,
+    lines: Receiver<String>,
+    timeout: Duration,
+    fallback: Box<dyn Agent>,
+    /// Of the last request sent
+    seq: u64,
+}
+
+impl ExternalAgent {
+    pub fn spawn(config: &ExternalConfig, fallback: Box<dyn Agent>) -> io::Result<Self> {
+        let (program, args) = config
+            .command
+            .split_first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "No command given"))?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        // Write on a thread of its own so a bot that stops reading can't block the game
+        let (requests, pending) = mpsc::sync_channel::<String>(1);
+        if let Some(mut stdin) = child.stdin.take() {
+            thread::spawn(move || {
+                for line in pending {
+                    if stdin
+                        .write_all(line.as_bytes())
+                        .and_then(|_| stdin.flush())
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // And read on another so a silent bot can be timed out
+        let (sender, lines) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        Ok(Self {
+            requests: Some(requests),
+            child,
+            lines,
+            timeout: Duration::from_millis(config.timeout_ms),
+            fallback,
+            seq: 0,
+        })
+    }
+
+    fn ask(&mut self, view: &GameView<'_>, pending: &PendingDecision) -> Result<Action, BotError> {
+        // Answers turning up after a timeout were meant for an earlier decision
+        while self.lines.try_recv().is_ok() {}
+
+        self.seq += 1;
+        let request = Request::new(view, pending).with_seq(self.seq);
+        let requests = self.requests.as_ref().ok_or(BotError::Exited)?;
+        let mut line =
+            serde_json::to_string(&request).map_err(|e| BotError::Unreadable(e.to_string()))?;
+        line.push('\n');
+        match requests.try_send(line) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => return Err(BotError::NotReading),
+            Err(TrySendError::Disconnected(_)) => {
+                self.requests = None;
+                return Err(BotError::Exited);
+            }
+        }
+
+        let deadline = Instant::now() + self.timeout;
+        let reply = loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(left) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => return Err(BotError::Timeout(self.timeout)),
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.requests = None;
+                    return Err(BotError::Exited);
+                }
+            };
+            match serde_json::from_str::<Answer>(line.trim()) {
+                Ok(answer) if answer.seq == self.seq => break answer.action,
+                Ok(answer) => log::debug!(
+                    "Ignoring an answer to request {} [Player={}]",
+                    answer.seq,
+                    pending.player
+                ),
+                Err(_) => return Err(BotError::Unreadable(line)),
+            }
+        };
+        let action = match reply {
+            Reply::Index(i) => request
+                .legal_actions
+                .get(i)
+                .cloned()
+                .ok_or(BotError::NoSuchIndex(i))?,
+            Reply::Action(action) => action,
+        };
+        if view.is_legal(&action) {
+            Ok(action)
+        } else {
+            Err(BotError::Illegal(action))
+        }
+    }
+
+    /// Whether the process is still taking requests
+    pub const fn is_running(&self) -> bool {
+        self.requests.is_some()
+    }
+}
+
+impl Agent for ExternalAgent {
+    fn decide(&mut self, view: &GameView<'_>, pending: &PendingDecision) -> Action {
+        if self.is_running() {
+            match self.ask(view, pending) {
+                Ok(action) => return action,
+                Err(e) => log::warn!("{}, falling back [Player={}]", e, pending.player),
+            }
+        }
+        self.fallback.decide(view, pending)
+    }
+}
+
+impl Drop for ExternalAgent {
+    fn drop(&mut self) {
+        // Closing stdin lets a well behaved bot finish on its own, the rest are killed
+        self.requests = None;
+        let deadline = Instant::now() + EXIT_GRACE;
+        while let Ok(None) = self.child.try_wait() {
+            if Instant::now() >= deadline {
+                let _ = self.child.kill();
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use crate::{action::Action, agent::Agent, scenario::Scenario, strategy::Strategy};
+
+    use super::{Answer, ExternalAgent, ExternalConfig, Reply};
+
+    fn bot(script: &str) -> ExternalAgent {
+        let config = ExternalConfig::new(&["sh", "-c", script]).with_timeout_ms(200);
+        ExternalAgent::spawn(&config, Strategy::Greedy.agent(0)).unwrap()
+    }
+
+    /// Shell reading a request and answering it with `action`
+    fn answer(action: &str) -> String {
+        format!(
+            r#"read line; seq=$(echo "$line" | sed 's/^{{"seq":\([0-9]*\).*/\1/'); echo "{{\"seq\":$seq,\"action\":{}}}"; "#,
+            action.replace('"', r#"\""#)
+        )
+    }
+
+    #[test]
+    fn replies_are_indices_or_actions() {
+        assert_eq!(serde_json::from_str::<Reply>("2").unwrap(), Reply::Index(2));
+        assert_eq!(
+            serde_json::from_str::<Answer>(r#"{"seq": 3, "action": {"Bid": 40}}"#).unwrap(),
+            Answer {
+                seq: 3,
+                action: Reply::Action(Action::Bid(40))
+            }
+        );
+
+        // Whitechapel Road is for sale after a roll of 3
+        let mut game = Scenario::new(2).roll(&[1, 2]).build().unwrap();
+        let mut agent = bot(&format!(
+            "{}{}cat > /dev/null",
+            answer("0"),
+            answer(r#""Decline""#)
+        ));
+        let pending = game.pending().unwrap();
+        assert_eq!(agent.decide(&game.view(), &pending), Action::Roll);
+        game.apply(Action::Roll).unwrap();
+        let pending = game.pending().unwrap();
+        assert_eq!(agent.decide(&game.view(), &pending), Action::Decline);
+        assert!(agent.is_running());
+    }
+
+    #[test]
+    fn misbehaving_bots_are_covered_by_the_fallback() {
+        let mut game = Scenario::new(2).roll(&[1, 2]).build().unwrap();
+        game.apply(Action::Roll).unwrap();
+        let pending = game.pending().unwrap();
+        let expected = Strategy::Greedy.agent(0).decide(&game.view(), &pending);
+        assert_eq!(expected, Action::Buy);
+
+        for script in [
+            "exit 1".to_string(),
+            "read line; sleep 2".to_string(),
+            "read line; echo nonsense; cat > /dev/null".to_string(),
+            format!("{}cat > /dev/null", answer("99")),
+            format!(r#"{}cat > /dev/null"#, answer(r#""PayBail""#)),
+            // Answers that don't name the request are left unread
+            "read line; echo 1; cat > /dev/null".to_string(),
+            r#"read line; echo '{"seq": 9, "action": 1}'; cat > /dev/null"#.to_string(),
+        ] {
+            let mut agent = bot(&script);
+            assert_eq!(agent.decide(&game.view(), &pending), expected, "{}", script);
+        }
+
+        let mut agent = bot("exit 1");
+        agent.decide(&game.view(), &pending);
+        assert!(!agent.is_running());
+
+        // A late answer to the first request isn't taken for the second
+        let mut agent = bot(&format!(
+            r#"read line; sleep 0.25; echo '{{"seq": 1, "action": 1}}'; {}cat > /dev/null"#,
+            answer(r#""Decline""#)
+        ));
+        assert_eq!(agent.decide(&game.view(), &pending), expected);
+        assert_eq!(agent.decide(&game.view(), &pending), Action::Decline);
+    }
+
+    #[test]
+    fn bots_that_stop_reading_dont_block_the_game() {
+        let mut game = Scenario::new(2).roll(&[1, 2]).build().unwrap();
+        game.apply(Action::Roll).unwrap();
+        let pending = game.pending().unwrap();
+
+        // Enough requests to fill the pipe, then the bot is killed on drop
+        let start = Instant::now();
+        let mut agent = bot("sleep 30");
+        for _ in 0..100 {
+            assert_eq!(agent.decide(&game.view(), &pending), Action::Buy);
+        }
+        drop(agent);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
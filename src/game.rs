@@ -0,0 +1,1636 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    action::{Action, Decision, IllegalAction, PendingDecision, TradeOffer},
+    agent::Agent,
+    config::BoardConfig,
+    dice::{Dice, NDice, RollState, SpeedFace},
+    error::MonopolyError,
+    event::Event,
+    invariants,
+    locations::{ColourGroup, SquareKind},
+    monopoly::{Bank, MonopolyState},
+    phase::Phase,
+    player::{Player, PlayerId, JAIL_BAIL},
+    portfolio::{Portfolio, Standing},
+    rules::IncomeTax,
+    square::{BoardPosition, BoardSquare, MoveTo},
+    state::State,
+    termination::EndReason,
+    view::GameView,
+};
+
+pub const GO_SALARY: usize = 200;
+/// Raises offered on top of the highest bid when listing auction actions
+pub const BID_INCREMENTS: [usize; 4] = [1, 10, 50, 100];
+
+const MAX_DOUBLES: usize = 3;
+const MAX_MANAGE_ACTIONS: usize = 16;
+const MAX_TRADES_PER_TURN: usize = 1;
+
+#[derive(Debug, Clone)]
+struct Auction {
+    position: BoardPosition,
+    bidders: Vec<PlayerId>,
+    next: usize,
+    highest: Option<(PlayerId, usize)>,
+}
+
+/// Who a charge is paid to
+#[derive(Debug, Clone, Copy)]
+enum Payee {
+    Bank,
+    /// Fines and taxes, collected by whoever lands on free parking
+    Pot,
+    Player(PlayerId),
+}
+
+impl Payee {
+    const fn creditor(self) -> Option<PlayerId> {
+        match self {
+            Self::Player(id) => Some(id),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Stage {
+    StartTurn,
+    PreRoll,
+    Jail,
+    Roll,
+    Buy(BoardPosition),
+    Auction(Auction),
+    IncomeTax {
+        flat: usize,
+        percentage: usize,
+    },
+    /// White dice of a bus roll
+    Bus(Vec<usize>),
+    Manage,
+    TradeResponse(TradeOffer),
+    GameOver,
+}
+
+/// The rules engine. It runs on its own until a player has to make a decision,
+/// then waits for that decision to be supplied through `apply`.
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub board: Vec<BoardSquare>,
+    pub players: Vec<Player>,
+    pub state: MonopolyState,
+    turn: usize,
+    phase: Phase,
+    stage: Stage,
+    doubles: usize,
+    rolled_double: bool,
+    /// Speed die showed Mr. Monopoly on the current roll
+    mr_monopoly: bool,
+    /// Squares moved by the latest roll, which utility rent is a multiple of
+    dice_total: usize,
+    /// See `BoardConfig::chance_destinations`
+    chance_destinations: Vec<Option<BoardPosition>>,
+    dice: Box<dyn Dice>,
+    manage_actions: usize,
+    trades: usize,
+    recording: bool,
+    events: Vec<Event>,
+    checking: bool,
+    /// Bankrupt players, first out first
+    eliminated: Vec<PlayerId>,
+    end_reason: Option<EndReason>,
+    /// Money passed between players since the round started
+    changed_hands: bool,
+    quiet_rounds: usize,
+}
+
+impl Game {
+    pub fn new(n_players: usize, seed: u64) -> Self {
+        Self::with_board(
+            Player::create_players(n_players),
+            &BoardConfig::uk(),
+            MonopolyState::seeded(seed),
+        )
+    }
+
+    pub fn with_board(players: Vec<Player>, config: &BoardConfig, state: MonopolyState) -> Self {
+        Self::with_dice(players, config, state, Box::new(NDice::standard()))
+    }
+
+    pub fn with_dice(
+        players: Vec<Player>,
+        config: &BoardConfig,
+        state: MonopolyState,
+        dice: Box<dyn Dice>,
+    ) -> Self {
+        let mut game = Self::setup(players, config, state, dice);
+        // Every way out of `Phase::StartTurn` is a valid transition
+        if let Err(e) = game.start() {
+            log::error!("{} [Player={}]", e, game.current_player());
+        }
+        game
+    }
+
+    /// The game before the first roll, so the position can be changed first
+    pub(crate) fn setup(
+        players: Vec<Player>,
+        config: &BoardConfig,
+        state: MonopolyState,
+        dice: Box<dyn Dice>,
+    ) -> Self {
+        let board = config
+            .squares
+            .iter()
+            .enumerate()
+            .map(|(position, details)| BoardSquare::from_property(position, details))
+            .collect();
+
+        Self {
+            board,
+            players,
+            state,
+            turn: 0,
+            phase: Phase::StartTurn,
+            stage: Stage::StartTurn,
+            doubles: 0,
+            rolled_double: false,
+            mr_monopoly: false,
+            dice_total: 0,
+            chance_destinations: config.chance_positions(),
+            dice,
+            manage_actions: 0,
+            trades: 0,
+            recording: false,
+            events: vec![],
+            checking: false,
+            eliminated: vec![],
+            end_reason: None,
+            changed_hands: false,
+            quiet_rounds: 0,
+        }
+    }
+
+    /// Run on from `setup` to the first decision
+    pub(crate) fn start(&mut self) -> Result<(), MonopolyError> {
+        self.state.bank = Bank {
+            opening: self.total_cash(),
+            ..Bank::default()
+        };
+        self.advance()
+    }
+
+    /// Whose turn it is, only meant for setting up a position
+    pub(crate) fn set_turn(&mut self, id: PlayerId) -> Result<(), MonopolyError> {
+        self.turn = self.seat(id)?;
+        Ok(())
+    }
+
+    /// Re-draw all future dice rolls and cards, scripted ones included
+    pub fn reseed(&mut self, seed: u64) {
+        self.state.reseed(seed);
+        self.dice = self.dice.unscripted();
+    }
+
+    /// Keep a log of everything that happens from now on. Off by default so
+    /// that searches cloning the game don't copy the history around.
+    pub fn record_events(&mut self, on: bool) {
+        self.recording = on;
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Hand over the events logged so far, leaving the log empty
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Run `invariants::check` at the end of every turn and panic with the
+    /// diagnostic if anything is wrong. Meant for tests and debugging.
+    pub fn check_invariants(&mut self, on: bool) {
+        self.checking = on;
+    }
+
+    /// Cash held by the players plus the free parking pot
+    pub fn total_cash(&self) -> usize {
+        self.players
+            .iter()
+            .map(Player::current_balance)
+            .sum::<usize>()
+            + self.state.free_parking.borrow().0
+    }
+
+    fn record(&mut self, event: Event) {
+        if self.recording {
+            self.events.push(event);
+        }
+    }
+
+    pub fn player(&self, id: PlayerId) -> Result<&Player, MonopolyError> {
+        Ok(&self.players[self.seat(id)?])
+    }
+
+    /// Index of the player in turn order
+    pub fn seat(&self, id: PlayerId) -> Result<usize, MonopolyError> {
+        self.players
+            .iter()
+            .position(|p| p.id == id)
+            .ok_or(MonopolyError::UnknownPlayer(id))
+    }
+
+    pub fn current_player(&self) -> PlayerId {
+        self.players[self.turn].id
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.state.cycles()
+    }
+
+    /// What agents get to see of the game
+    pub const fn view(&self) -> GameView<'_> {
+        GameView::new(self)
+    }
+
+    pub const fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn is_over(&self) -> bool {
+        matches!(self.stage, Stage::GameOver)
+    }
+
+    pub fn active_players(&self) -> impl Iterator<Item = &Player> {
+        self.players.iter().filter(|p| p.is_active())
+    }
+
+    /// The last player standing, or the richest when the game was stopped early
+    pub fn winner(&self) -> Option<PlayerId> {
+        if self.is_over() {
+            self.leaderboard().first().map(|s| s.player)
+        } else {
+            None
+        }
+    }
+
+    /// Which end condition finished the game
+    pub const fn end_reason(&self) -> Option<EndReason> {
+        self.end_reason
+    }
+
+    /// Players gone bankrupt, in the order they went
+    pub fn eliminated(&self) -> &[PlayerId] {
+        &self.eliminated
+    }
+
+    /// Everything the player owns, read off the board
+    pub fn portfolio(&self, id: PlayerId) -> Result<Portfolio, MonopolyError> {
+        Ok(self.holdings(self.player(id)?))
+    }
+
+    /// `portfolio` for one of the game's own `players`
+    pub fn holdings(&self, player: &Player) -> Portfolio {
+        Portfolio::new(player.id, player.current_balance(), &self.board)
+    }
+
+    /// Cash plus the price of every property held (mortgage value if mortgaged) and its buildings
+    pub fn net_worth(&self, id: PlayerId) -> Result<usize, MonopolyError> {
+        Ok(self.portfolio(id)?.net_worth())
+    }
+
+    /// Players still in the game by net worth, then those gone bankrupt, last out first
+    pub fn leaderboard(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self
+            .players
+            .iter()
+            .map(|p| Standing {
+                rank: 0,
+                player: p.id,
+                name: p.name.clone(),
+                active: p.is_active(),
+                cash: p.current_balance(),
+                net_worth: self.holdings(p).net_worth(),
+            })
+            .collect();
+        let out = |id| self.eliminated.iter().position(|&p| p == id);
+        standings.sort_by_key(|s| {
+            (
+                !s.active,
+                std::cmp::Reverse(s.net_worth),
+                std::cmp::Reverse(out(s.player)),
+            )
+        });
+        for (rank, standing) in standings.iter_mut().enumerate() {
+            standing.rank = rank + 1;
+        }
+        standings
+    }
+
+    pub fn owns_colour_group(&self, id: PlayerId, group: ColourGroup) -> bool {
+        self.group(group).all(|sq| sq.owner_id() == Some(id))
+    }
+
+    /// What landing on `position` costs with `dice_total` thrown, before any mortgage
+    /// is taken into account
+    pub fn rent(&self, position: BoardPosition, dice_total: usize) -> usize {
+        let square = &self.board[position];
+        let (owned, full_set) = match (square.owner_id(), square.colour_group()) {
+            (Some(owner), Some(group)) => {
+                let owned = self
+                    .group(group)
+                    .filter(|sq| sq.owner_id() == Some(owner))
+                    .count();
+                (owned, owned == self.group(group).count())
+            }
+            _ => (1, false),
+        };
+        square.rent_cost(owned, full_set, dice_total)
+    }
+
+    fn group(&self, group: ColourGroup) -> impl Iterator<Item = &BoardSquare> {
+        self.board
+            .iter()
+            .filter(move |sq| sq.colour_group() == Some(group))
+    }
+
+    /// Fewest and most houses on any square of the group, a hotel counting as five
+    fn group_house_range(&self, group: ColourGroup) -> (usize, usize) {
+        self.group(group)
+            .map(BoardSquare::house_count)
+            .fold((usize::MAX, 0), |(min, max), n| (min.min(n), max.max(n)))
+    }
+
+    fn group_has_buildings(&self, position: BoardPosition) -> bool {
+        match self.board[position].colour_group() {
+            Some(group) => self.group(group).any(|sq| sq.house_count() > 0),
+            None => false,
+        }
+    }
+
+    fn jail_position(&self) -> BoardPosition {
+        self.board
+            .iter()
+            .position(|sq| sq.kind() == SquareKind::Jail)
+            .unwrap_or_default()
+    }
+
+    pub fn can_build(&self, id: PlayerId, position: BoardPosition) -> bool {
+        let sq = &self.board[position];
+        match sq.colour_group() {
+            Some(group) if group.is_buildable() => {
+                // Build evenly: no square may get two houses ahead of another in the group
+                self.owns_colour_group(id, group)
+                    && self.group(group).all(|s| !s.is_mortgaged())
+                    && sq.house_count() == self.group_house_range(group).0
+                    && sq.upgradable()
+                    && self
+                        .player(id)
+                        .is_ok_and(|p| p.can_afford(sq.upgrade_cost()))
+            }
+            _ => false,
+        }
+    }
+
+    /// Buildings are sold evenly too, from the squares with the most houses first
+    pub fn can_sell_building(&self, id: PlayerId, position: BoardPosition) -> bool {
+        let sq = &self.board[position];
+        match sq.colour_group() {
+            Some(group) if sq.owner_id() == Some(id) && sq.house_count() > 0 => {
+                sq.house_count() == self.group_house_range(group).1
+            }
+            _ => false,
+        }
+    }
+
+    pub fn can_mortgage(&self, id: PlayerId, position: BoardPosition) -> bool {
+        let sq = &self.board[position];
+        sq.owner_id() == Some(id) && !sq.is_mortgaged() && !self.group_has_buildings(position)
+    }
+
+    pub fn can_unmortgage(&self, id: PlayerId, position: BoardPosition) -> bool {
+        let sq = &self.board[position];
+        sq.owner_id() == Some(id)
+            && sq.is_mortgaged()
+            && self
+                .player(id)
+                .is_ok_and(|p| p.can_afford(sq.unmortgage_cost()))
+    }
+
+    /// Whether the player whose turn it is may still put a trade to another player
+    pub fn can_trade(&self) -> bool {
+        matches!(self.stage, Stage::PreRoll | Stage::Manage)
+            && self.trades < MAX_TRADES_PER_TURN
+            && self.manage_actions < MAX_MANAGE_ACTIONS
+    }
+
+    fn valid_offer(&self, offer: &TradeOffer) -> bool {
+        let tradeable = |owner: PlayerId, positions: &[BoardPosition]| {
+            positions.iter().all(|&p| {
+                p < self.board.len()
+                    && self.board[p].owner_id() == Some(owner)
+                    && !self.group_has_buildings(p)
+            })
+        };
+        let affords = |id: PlayerId, cash: usize| self.player(id).is_ok_and(|p| p.can_afford(cash));
+
+        offer.from == self.current_player()
+            && offer.from != offer.to
+            && self
+                .players
+                .iter()
+                .any(|p| p.id == offer.to && p.is_active())
+            && !(offer.give.is_empty() && offer.take.is_empty())
+            && tradeable(offer.from, &offer.give)
+            && tradeable(offer.to, &offer.take)
+            && affords(offer.from, offer.give_cash)
+            && affords(offer.to, offer.take_cash)
+    }
+
+    pub fn pending(&self) -> Option<PendingDecision> {
+        let current = self.current_player();
+        let (player, decision) = match &self.stage {
+            Stage::PreRoll => (current, Decision::PreRoll),
+            Stage::Jail => (current, Decision::Jail),
+            Stage::Buy(position) => (
+                current,
+                Decision::Buy {
+                    position: *position,
+                    price: self.board[*position].get_purchase_cost(),
+                },
+            ),
+            Stage::Auction(auction) => (
+                auction.bidders[auction.next],
+                Decision::Bid {
+                    position: auction.position,
+                    highest: auction.highest.map_or(0, |(_, bid)| bid),
+                },
+            ),
+            Stage::IncomeTax { flat, percentage } => (
+                current,
+                Decision::IncomeTax {
+                    flat: *flat,
+                    percentage: *percentage,
+                },
+            ),
+            Stage::Bus(faces) => (
+                current,
+                Decision::Bus {
+                    faces: faces.clone(),
+                },
+            ),
+            Stage::Manage => (current, Decision::Manage),
+            Stage::TradeResponse(offer) => (
+                offer.to,
+                Decision::TradeResponse {
+                    offer: offer.clone(),
+                },
+            ),
+            _ => return None,
+        };
+
+        Some(PendingDecision { player, decision })
+    }
+
+    /// Every action the engine would accept right now, the do-nothing choice first.
+    /// Bids and trade proposals are open-ended: a few bids and an offer to buy or sell
+    /// each tradeable square at its price are listed, any other valid one is accepted.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        match self.pending() {
+            Some(pending) => self.legal_actions_for(pending.player),
+            None => vec![],
+        }
+    }
+
+    /// What `id` may do right now, which is nothing unless the game is waiting on them
+    pub fn legal_actions_for(&self, id: PlayerId) -> Vec<Action> {
+        let pending = match self.pending() {
+            Some(pending) if pending.player == id => pending,
+            _ => return vec![],
+        };
+        let Ok(player) = self.player(id) else {
+            return vec![];
+        };
+
+        match pending.decision {
+            Decision::Jail => {
+                let mut actions = vec![Action::RollForDoubles];
+                if player.can_afford(JAIL_BAIL) {
+                    actions.push(Action::PayBail);
+                }
+                if player.has_jail_card() {
+                    actions.push(Action::UseJailCard);
+                }
+                actions
+            }
+            Decision::Buy { price, .. } => {
+                let mut actions = vec![Action::Decline];
+                if player.can_afford(price) {
+                    actions.push(Action::Buy);
+                }
+                actions
+            }
+            Decision::Bid { highest, .. } => {
+                let mut actions = vec![Action::Pass];
+                actions.extend(
+                    BID_INCREMENTS
+                        .iter()
+                        .map(|raise| highest + raise)
+                        .filter(|&bid| player.can_afford(bid))
+                        .map(Action::Bid),
+                );
+                actions
+            }
+            Decision::PreRoll => {
+                let mut actions = vec![Action::Roll];
+                actions.extend(self.management_actions(id));
+                actions.extend(self.trade_proposals(id));
+                actions
+            }
+            Decision::Manage => {
+                let mut actions = vec![Action::EndTurn];
+                actions.extend(self.management_actions(id));
+                actions.extend(self.trade_proposals(id));
+                actions
+            }
+            Decision::TradeResponse { .. } => vec![Action::RejectTrade, Action::AcceptTrade],
+            Decision::IncomeTax { .. } => vec![Action::PayFlatTax, Action::PayTaxPercentage],
+            Decision::Bus { faces } => {
+                let mut actions = vec![Action::MoveBy(faces.iter().sum())];
+                for &face in &faces {
+                    if !actions.contains(&Action::MoveBy(face)) {
+                        actions.push(Action::MoveBy(face));
+                    }
+                }
+                actions
+            }
+        }
+    }
+
+    /// Any bid from here up to everything the bidder can afford is accepted
+    pub fn bid_range(&self) -> Option<RangeInclusive<usize>> {
+        match self.pending()? {
+            PendingDecision {
+                player,
+                decision: Decision::Bid { highest, .. },
+            } => {
+                let most = self.player(player).ok()?.current_balance();
+                Some(highest + 1..=most).filter(|range| !range.is_empty())
+            }
+            _ => None,
+        }
+    }
+
+    /// Builds, sales, mortgages and unmortgages open to the player, before or after rolling
+    fn management_actions(&self, id: PlayerId) -> Vec<Action> {
+        let mut actions = vec![];
+        if self.manage_actions < MAX_MANAGE_ACTIONS {
+            for position in 0..self.board.len() {
+                if self.can_build(id, position) {
+                    actions.push(Action::Build(position));
+                }
+                if self.can_sell_building(id, position) {
+                    actions.push(Action::SellBuilding(position));
+                }
+                if self.can_mortgage(id, position) {
+                    actions.push(Action::Mortgage(position));
+                }
+                if self.can_unmortgage(id, position) {
+                    actions.push(Action::Unmortgage(position));
+                }
+            }
+        }
+        actions
+    }
+
+    /// An offer to buy each square another player could trade away at its price,
+    /// and to sell each of the player's own to anyone who can pay for it
+    fn trade_proposals(&self, id: PlayerId) -> Vec<Action> {
+        if !self.can_trade() {
+            return vec![];
+        }
+
+        let mut offers = vec![];
+        for other in self.active_players().filter(|p| p.id != id) {
+            for (position, sq) in self.board.iter().enumerate() {
+                let price = sq.get_purchase_cost();
+                let offer = match sq.owner_id() {
+                    Some(owner) if owner == other.id => TradeOffer {
+                        from: id,
+                        to: owner,
+                        give: vec![],
+                        take: vec![position],
+                        give_cash: price,
+                        take_cash: 0,
+                    },
+                    Some(owner) if owner == id => TradeOffer {
+                        from: id,
+                        to: other.id,
+                        give: vec![position],
+                        take: vec![],
+                        give_cash: 0,
+                        take_cash: price,
+                    },
+                    _ => continue,
+                };
+                if self.valid_offer(&offer) {
+                    offers.push(Action::ProposeTrade(offer));
+                }
+            }
+        }
+        offers
+    }
+
+    pub fn is_legal(&self, action: &Action) -> bool {
+        match (self.pending(), action) {
+            (None, _) => false,
+            (
+                Some(PendingDecision {
+                    player,
+                    decision: Decision::Bid { highest, .. },
+                }),
+                Action::Bid(bid),
+            ) => *bid > highest && self.player(player).is_ok_and(|p| p.can_afford(*bid)),
+            (Some(_), Action::ProposeTrade(offer)) => self.can_trade() && self.valid_offer(offer),
+            (Some(_), action) => self.legal_actions().contains(action),
+        }
+    }
+
+    /// Apply the response to the pending decision and run the game on to the next one
+    pub fn apply(&mut self, action: Action) -> Result<(), MonopolyError> {
+        if !self.is_legal(&action) {
+            return Err(IllegalAction {
+                player: self.pending().map_or(0, |p| p.player),
+                action,
+            }
+            .into());
+        }
+
+        self.perform(action)?;
+        self.advance()?;
+        Ok(())
+    }
+
+    /// Apply the action, or the do-nothing choice if the engine won't accept it.
+    /// Fails once there is no decision left to make.
+    pub fn apply_or_default(&mut self, mut action: Action) -> Result<(), MonopolyError> {
+        let player = match self.pending() {
+            Some(pending) => pending.player,
+            None => return Err(IllegalAction { player: 0, action }.into()),
+        };
+        if !self.is_legal(&action) {
+            log::warn!("Illegal action {:?} [Player={}]", action, player);
+            action = self.legal_actions().swap_remove(0);
+        }
+        if let Err(e) = self.perform(action) {
+            log::error!("{} [Player={}]", e, self.players[self.turn].id);
+        }
+        self.advance()
+    }
+
+    /// Let the agents (one per seat) play until the game ends or `max_rounds` have passed
+    pub fn play(&mut self, agents: &mut [Box<dyn Agent>], max_rounds: usize) -> Option<PlayerId> {
+        while let Some(pending) = self.pending() {
+            if self.rounds() >= max_rounds {
+                break;
+            }
+
+            if let Err(e) = self.step(agents, &pending) {
+                log::error!("{}, abandoning the game [Player={}]", e, pending.player);
+                break;
+            }
+        }
+
+        self.winner()
+    }
+
+    /// Let the agents play out the current player's turn, along with any auction or
+    /// trade reply it leads to. False once the game is over.
+    pub fn play_turn(&mut self, agents: &mut [Box<dyn Agent>]) -> bool {
+        let player = self.current_player();
+        while let Some(pending) = self.pending() {
+            if self.current_player() != player {
+                break;
+            }
+
+            if let Err(e) = self.step(agents, &pending) {
+                log::error!("{}, abandoning the game [Player={}]", e, pending.player);
+                return false;
+            }
+        }
+
+        !self.is_over()
+    }
+
+    /// Ask the agent in the pending player's seat and apply its answer
+    fn step(
+        &mut self,
+        agents: &mut [Box<dyn Agent>],
+        pending: &PendingDecision,
+    ) -> Result<(), MonopolyError> {
+        let seat = self.seat(pending.player)?;
+        let action = agents[seat].decide(&self.view(), pending);
+        self.apply_or_default(action)
+    }
+
+    fn perform(&mut self, action: Action) -> Result<(), MonopolyError> {
+        let seat = self.turn;
+        match action {
+            Action::PayBail => {
+                self.charge(seat, JAIL_BAIL, Payee::Pot);
+                self.players[seat].get_out_of_jail();
+                self.released(seat);
+                self.stage = Stage::Roll;
+            }
+            Action::UseJailCard => {
+                self.players[seat].use_jail_card();
+                self.released(seat);
+                self.stage = Stage::Roll;
+            }
+            Action::RollForDoubles => self.roll_in_jail()?,
+            Action::Buy => {
+                if let Stage::Buy(position) = self.stage {
+                    let price = self.board[position].get_purchase_cost();
+                    self.board[position].purchase_property(&self.players[seat])?;
+                    self.state.bank.received += price;
+                    self.record(Event::Bought {
+                        player: self.players[seat].id,
+                        position,
+                        price,
+                    });
+                }
+                self.after_landing()?;
+            }
+            Action::Decline => {
+                if let Stage::Buy(position) = self.stage {
+                    self.start_auction(position);
+                }
+            }
+            Action::Bid(bid) => {
+                if let Stage::Auction(auction) = &mut self.stage {
+                    auction.highest = Some((auction.bidders[auction.next], bid));
+                    auction.next = (auction.next + 1) % auction.bidders.len();
+                }
+                self.check_auction()?;
+            }
+            Action::Pass => {
+                if let Stage::Auction(auction) = &mut self.stage {
+                    auction.bidders.remove(auction.next);
+                    if auction.next >= auction.bidders.len() {
+                        auction.next = 0;
+                    }
+                }
+                self.check_auction()?;
+            }
+            Action::Build(position) => {
+                self.state.bank.received += self.board[position].upgrade_cost();
+                self.board[position].purchase_upgrade(&self.players[seat])?;
+                self.manage_actions += 1;
+                let player = self.players[seat].id;
+                self.record(Event::Built { player, position });
+            }
+            Action::SellBuilding(position) => {
+                let refund = self.board[position].sell_upgrade(&self.players[seat]);
+                self.state.bank.paid_out += refund;
+                self.manage_actions += 1;
+                let player = self.players[seat].id;
+                self.record(Event::SoldBuilding {
+                    player,
+                    position,
+                    refund,
+                });
+            }
+            Action::Mortgage(position) => {
+                self.state.bank.paid_out += self.board[position].mortgage_value();
+                self.board[position].mortgage(&self.players[seat])?;
+                self.manage_actions += 1;
+                let player = self.players[seat].id;
+                self.record(Event::Mortgaged { player, position });
+            }
+            Action::Unmortgage(position) => {
+                self.state.bank.received += self.board[position].unmortgage_cost();
+                self.board[position].unmortgage(&self.players[seat])?;
+                self.manage_actions += 1;
+                let player = self.players[seat].id;
+                self.record(Event::Unmortgaged { player, position });
+            }
+            Action::ProposeTrade(offer) => {
+                self.trades += 1;
+                self.manage_actions += 1;
+                self.stage = Stage::TradeResponse(offer);
+            }
+            Action::AcceptTrade => {
+                if let Stage::TradeResponse(offer) = &self.stage {
+                    let offer = offer.clone();
+                    self.trade(&offer)?;
+                    self.changed_hands |= offer.give_cash + offer.take_cash > 0;
+                    self.record(Event::Traded { offer });
+                }
+                self.stage = self.management_stage();
+            }
+            Action::RejectTrade => self.stage = self.management_stage(),
+            Action::Roll => {
+                self.stage = if self.players[seat].in_jail() {
+                    self.enter(Phase::Jail)?;
+                    Stage::Jail
+                } else {
+                    Stage::Roll
+                };
+            }
+            Action::PayFlatTax => self.pay_income_tax(false)?,
+            Action::PayTaxPercentage => self.pay_income_tax(true)?,
+            Action::MoveBy(steps) => {
+                self.dice_total = steps;
+                self.move_forward(seat, steps)?;
+            }
+            Action::EndTurn => self.end_turn()?,
+        }
+        Ok(())
+    }
+
+    /// Run every step that doesn't need a decision
+    fn advance(&mut self) -> Result<(), MonopolyError> {
+        loop {
+            match self.stage {
+                Stage::StartTurn => self.start_turn()?,
+                Stage::Roll => self.roll()?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn start_turn(&mut self) -> Result<(), MonopolyError> {
+        if self.active_players().count() <= 1 {
+            self.game_over(EndReason::LastStanding)?;
+            return Ok(());
+        }
+
+        self.doubles = 0;
+        self.rolled_double = false;
+        self.mr_monopoly = false;
+        self.manage_actions = 0;
+        self.trades = 0;
+        self.enter(Phase::PreRoll)?;
+        self.stage = Stage::PreRoll;
+        Ok(())
+    }
+
+    /// Move the turn on to `to`, which has to be one of `phase::TRANSITIONS`
+    fn enter(&mut self, to: Phase) -> Result<(), MonopolyError> {
+        self.phase = self.phase.transition(to)?;
+        Ok(())
+    }
+
+    fn post_roll(&mut self) -> Result<(), MonopolyError> {
+        self.enter(Phase::PostRoll)?;
+        self.stage = Stage::Manage;
+        Ok(())
+    }
+
+    /// Where a trade goes back to once it has been answered
+    const fn management_stage(&self) -> Stage {
+        match self.phase {
+            Phase::PreRoll => Stage::PreRoll,
+            _ => Stage::Manage,
+        }
+    }
+
+    fn roll(&mut self) -> Result<(), MonopolyError> {
+        let seat = self.turn;
+        self.enter(Phase::Roll)?;
+        let roll_result = self.dice.roll(&mut self.state.rng);
+        self.rolled(seat, &roll_result);
+        if roll_result.is_double {
+            self.doubles += 1;
+            if self.doubles == MAX_DOUBLES {
+                log::info!(
+                    "Three doubles, go to jail [Player={}]",
+                    self.players[seat].id
+                );
+                self.send_to_jail(seat);
+                self.post_roll()?;
+                return Ok(());
+            }
+        }
+
+        self.rolled_double = roll_result.is_double;
+        self.mr_monopoly = roll_result.speed == Some(SpeedFace::MrMonopoly);
+        if roll_result.speed == Some(SpeedFace::Bus) && roll_result.faces.len() > 1 {
+            self.stage = Stage::Bus(roll_result.faces);
+            return Ok(());
+        }
+        self.dice_total = roll_result.value;
+        self.move_forward(seat, roll_result.value)?;
+        Ok(())
+    }
+
+    fn roll_in_jail(&mut self) -> Result<(), MonopolyError> {
+        let seat = self.turn;
+        // Only the numbered dice count when rolling to get out of jail
+        let roll_result = self.dice.roll(&mut self.state.rng);
+        let steps: usize = roll_result.faces.iter().sum();
+        self.rolled(seat, &roll_result);
+        let days = self.players[seat].go_to_jail();
+        self.rolled_double = false;
+        self.dice_total = steps;
+
+        if roll_result.is_double {
+            self.players[seat].get_out_of_jail();
+            self.released(seat);
+            self.move_forward(seat, steps)?;
+        } else if days >= 3 {
+            self.charge(seat, JAIL_BAIL, Payee::Pot);
+            if self.players[seat].is_active() {
+                self.players[seat].get_out_of_jail();
+                self.released(seat);
+                self.move_forward(seat, steps)?;
+            } else {
+                self.end_turn()?;
+            }
+        } else {
+            self.post_roll()?;
+        }
+        Ok(())
+    }
+
+    fn send_to_jail(&mut self, seat: usize) {
+        let jail = self.jail_position();
+        let player = &self.players[seat];
+        player.go_to_jail();
+        player.set_position(jail);
+        self.rolled_double = false;
+        self.mr_monopoly = false;
+        let player = player.id;
+        self.record(Event::Jailed { player });
+    }
+
+    fn rolled(&mut self, seat: usize, roll: &RollState) {
+        self.record(Event::Rolled {
+            player: self.players[seat].id,
+            faces: roll.faces.clone(),
+            value: roll.value,
+        });
+    }
+
+    fn released(&mut self, seat: usize) {
+        let player = self.players[seat].id;
+        self.record(Event::Released { player });
+    }
+
+    fn game_over(&mut self, reason: EndReason) -> Result<(), MonopolyError> {
+        self.enter(Phase::GameOver)?;
+        self.stage = Stage::GameOver;
+        self.end_reason = Some(reason);
+        let winner = self.winner();
+        self.record(Event::GameOver { winner });
+        Ok(())
+    }
+
+    fn move_forward(&mut self, seat: usize, steps: usize) -> Result<(), MonopolyError> {
+        self.enter(Phase::Move)?;
+        let player = &self.players[seat];
+        let (id, from) = (player.id, player.current_position());
+        let passed_go = player.advance(steps, self.board.len());
+        if passed_go {
+            self.pay_out(seat, GO_SALARY);
+        }
+        let to = self.players[seat].current_position();
+        self.record(Event::Moved {
+            player: id,
+            from,
+            to,
+        });
+        if passed_go {
+            self.record(Event::PassedGo { player: id });
+        }
+        self.land(seat)?;
+        Ok(())
+    }
+
+    fn land(&mut self, seat: usize) -> Result<(), MonopolyError> {
+        self.enter(Phase::Resolve)?;
+        let position = self.players[seat].current_position();
+        let id = self.players[seat].id;
+        let kind = self.board[position].kind();
+        let card = match kind {
+            SquareKind::Chance | SquareKind::CommunityChest => {
+                let card = self.state.draw_card(kind);
+                self.record(Event::Card {
+                    player: id,
+                    kind,
+                    card,
+                });
+                card
+            }
+            _ => 0,
+        };
+        let landing = {
+            let square = &self.board[position];
+            let rent = self.rent(position, self.dice_total);
+            square.take_step(square, &self.players[seat], card, rent)
+        };
+
+        let (flat, rate) = self.board[position].tax();
+        if kind == SquareKind::Tax && rate > 0 && self.state.rules.income_tax == IncomeTax::Choice {
+            let percentage = self.portfolio(id)?.assessed_value() * rate / 100;
+            self.stage = Stage::IncomeTax { flat, percentage };
+            return Ok(());
+        }
+
+        self.pay_out(seat, landing.collect);
+        let repairs = landing.repairs.map_or(0, |(per_house, per_hotel)| {
+            let (houses, hotels) = self.holdings(&self.players[seat]).buildings();
+            houses * per_house + hotels * per_hotel
+        });
+        if landing.fine + repairs > 0 {
+            self.charge(seat, landing.fine + repairs, Payee::Pot);
+        }
+        if let Some(payment) = landing.rent {
+            self.record(Event::Rent {
+                player: id,
+                owner: payment.to,
+                amount: payment.amount,
+            });
+            self.charge(seat, payment.amount, Payee::Player(payment.to));
+        }
+
+        if self.board[position].kind() == SquareKind::FreeParking {
+            let pot = std::mem::take(&mut self.state.free_parking.borrow_mut().0);
+            self.players[seat].deposit(pot);
+        }
+
+        // A card, tax or rent they couldn't cover
+        if !self.players[seat].is_active() {
+            self.end_turn()?;
+            return Ok(());
+        }
+
+        if let Some(destination) = landing.move_to {
+            if self.players[seat].in_jail() {
+                let jail = self.jail_position();
+                self.players[seat].set_position(jail);
+                self.record(Event::Jailed { player: id });
+                self.rolled_double = false;
+                self.post_roll()?;
+                return Ok(());
+            }
+
+            let destination = match destination {
+                MoveTo::Go => Some(0),
+                MoveTo::Jail => Some(self.jail_position()),
+                MoveTo::ChanceDestination(card) => {
+                    self.chance_destinations.get(card).copied().flatten()
+                }
+            };
+            if let Some(destination) = destination {
+                log::info!(
+                    "Moving to {} [Player={}]",
+                    self.board[destination].name(),
+                    id
+                );
+                let steps = (destination + self.board.len() - position) % self.board.len();
+                if steps > 0 {
+                    self.move_forward(seat, steps)?;
+                    return Ok(());
+                }
+            }
+        }
+
+        let square = &self.board[position];
+        if square.is_ownable() && !square.is_owned() {
+            self.stage = Stage::Buy(position);
+        } else {
+            self.after_landing()?;
+        }
+        Ok(())
+    }
+
+    fn pay_income_tax(&mut self, percentage: bool) -> Result<(), MonopolyError> {
+        if let Stage::IncomeTax {
+            flat,
+            percentage: share,
+        } = self.stage
+        {
+            let amount = if percentage { share } else { flat };
+            self.charge(self.turn, amount, Payee::Pot);
+        }
+        self.after_landing()?;
+        Ok(())
+    }
+
+    fn after_landing(&mut self) -> Result<(), MonopolyError> {
+        let seat = self.turn;
+        let player = &self.players[seat];
+        if std::mem::take(&mut self.mr_monopoly) && player.is_active() && !player.in_jail() {
+            if let Some(destination) = self.mr_monopoly_destination(seat) {
+                let position = self.players[seat].current_position();
+                let steps = (destination + self.board.len() - position) % self.board.len();
+                log::info!(
+                    "Mr. Monopoly moves {} squares [Player={}]",
+                    steps,
+                    player.id
+                );
+                self.move_forward(seat, steps)?;
+                return Ok(());
+            }
+        }
+
+        let player = &self.players[seat];
+        if !player.is_active() {
+            self.end_turn()?;
+        } else if self.rolled_double && !player.in_jail() {
+            self.enter(Phase::DoublesReroll)?;
+            self.stage = Stage::Roll;
+        } else {
+            self.post_roll()?;
+        }
+        Ok(())
+    }
+
+    /// The next unowned property, or failing that the next one the player owes rent on
+    fn mr_monopoly_destination(&self, seat: usize) -> Option<BoardPosition> {
+        let id = self.players[seat].id;
+        let position = self.players[seat].current_position();
+        let ahead: Vec<&BoardSquare> = (1..self.board.len())
+            .map(|step| &self.board[(position + step) % self.board.len()])
+            .filter(|sq| sq.is_ownable())
+            .collect();
+
+        ahead
+            .iter()
+            .find(|sq| !sq.is_owned())
+            .or_else(|| {
+                ahead
+                    .iter()
+                    .find(|sq| sq.owner_id() != Some(id) && !sq.is_mortgaged())
+            })
+            .map(|sq| sq.position())
+    }
+
+    fn start_auction(&mut self, position: BoardPosition) {
+        let n = self.players.len();
+        let bidders = (0..n)
+            .map(|offset| &self.players[(self.turn + offset) % n])
+            .filter(|p| p.is_active())
+            .map(|p| p.id)
+            .collect();
+
+        self.stage = Stage::Auction(Auction {
+            position,
+            bidders,
+            next: 0,
+            highest: None,
+        });
+    }
+
+    fn check_auction(&mut self) -> Result<(), MonopolyError> {
+        let (position, winner) = match &self.stage {
+            Stage::Auction(auction) => match (auction.bidders.as_slice(), auction.highest) {
+                ([], highest) => (auction.position, highest),
+                ([last], Some((id, bid))) if *last == id => (auction.position, Some((id, bid))),
+                _ => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+
+        if let Some((id, bid)) = winner {
+            log::info!(
+                "Won auction for {} with a bid of {} [Player={}]",
+                position,
+                bid,
+                id
+            );
+            self.board[position].auction_property(self.player(id)?, bid)?;
+            self.state.bank.received += bid;
+            self.record(Event::Bought {
+                player: id,
+                position,
+                price: bid,
+            });
+        }
+        self.after_landing()?;
+        Ok(())
+    }
+
+    fn pay_out(&mut self, seat: usize, amount: usize) {
+        self.players[seat].deposit(amount);
+        self.state.bank.paid_out += amount;
+    }
+
+    /// Take a payment, selling buildings and mortgaging to raise the money if needed
+    fn charge(&mut self, seat: usize, amount: usize, payee: Payee) {
+        if !self.players[seat].can_afford(amount) {
+            self.raise_funds(seat, amount);
+        }
+
+        if self.players[seat].pay(amount).is_err() {
+            self.bankrupt(seat, payee.creditor());
+            return;
+        }
+
+        match payee {
+            Payee::Bank => self.state.bank.received += amount,
+            Payee::Pot => self.state.free_parking.borrow_mut().0 += amount,
+            Payee::Player(id) => {
+                if let Ok(owner) = self.player(id) {
+                    owner.deposit(amount);
+                }
+                self.changed_hands = true;
+            }
+        }
+    }
+
+    fn raise_funds(&mut self, seat: usize, amount: usize) {
+        let (players, board, bank) = (&self.players, &self.board, &mut self.state.bank);
+        let player = &players[seat];
+        let owned = || {
+            board
+                .iter()
+                .filter(move |sq| sq.owner_id() == Some(player.id))
+        };
+
+        while !player.can_afford(amount) {
+            if let Some(sq) = owned()
+                .filter(|sq| sq.house_count() > 0)
+                .max_by_key(|sq| sq.house_count())
+            {
+                bank.paid_out += sq.sell_upgrade(player);
+            } else if let Some(sq) = owned().find(|sq| !sq.is_mortgaged()) {
+                if sq.mortgage(player).is_err() {
+                    break;
+                }
+                bank.paid_out += sq.mortgage_value();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Everything left goes to the creditor, or back to the bank
+    fn bankrupt(&mut self, seat: usize, creditor: Option<PlayerId>) {
+        let id = self.players[seat].id;
+        let cash = self.players[seat].declare_bankrupt();
+        log::info!("Bankrupt [Player={}]", id);
+        self.eliminated.push(id);
+        self.record(Event::Bankrupt {
+            player: id,
+            creditor,
+        });
+
+        match creditor {
+            Some(to) => {
+                if let Ok(creditor) = self.player(to) {
+                    creditor.deposit(cash);
+                }
+                self.changed_hands = true;
+            }
+            None => self.state.bank.received += cash,
+        }
+        // Buildings pass to the creditor, or are cleared when the bank takes the square
+        for holding in self.holdings(&self.players[seat]).holdings {
+            self.board[holding.position].transfer(creditor);
+        }
+    }
+
+    fn end_round(&mut self) {
+        self.state.update();
+        self.quiet_rounds = if self.changed_hands {
+            0
+        } else {
+            self.quiet_rounds + 1
+        };
+        self.changed_hands = false;
+    }
+
+    /// The first of the end conditions that has been met, if any
+    fn ending(&self) -> Option<EndReason> {
+        let end = self.state.rules.end;
+        if self.active_players().count() <= 1 {
+            Some(EndReason::LastStanding)
+        } else if end.first_bankruptcy && !self.eliminated.is_empty() {
+            Some(EndReason::FirstBankruptcy)
+        } else if end.max_rounds.is_some_and(|max| self.rounds() >= max) {
+            Some(EndReason::MaxRounds)
+        } else if end.stalemate_rounds.is_some_and(|k| self.quiet_rounds >= k) {
+            Some(EndReason::Stalemate)
+        } else {
+            None
+        }
+    }
+
+    fn trade(&self, offer: &TradeOffer) -> Result<(), MonopolyError> {
+        let from = self.player(offer.from)?;
+        let to = self.player(offer.to)?;
+        for &position in &offer.give {
+            self.board[position].transfer(Some(to.id));
+        }
+        for &position in &offer.take {
+            self.board[position].transfer(Some(from.id));
+        }
+        if offer.give_cash > 0 {
+            from.pay(offer.give_cash)?;
+            to.deposit(offer.give_cash);
+        }
+        if offer.take_cash > 0 {
+            to.pay(offer.take_cash)?;
+            from.deposit(offer.take_cash);
+        }
+        Ok(())
+    }
+
+    fn end_turn(&mut self) -> Result<(), MonopolyError> {
+        self.enter(Phase::EndTurn)?;
+        if self.checking {
+            if let Err(e) = invariants::check(self) {
+                panic!("Invariant violated at round {}: {}", self.rounds(), e);
+            }
+        }
+
+        if let Some(reason) = self.ending() {
+            self.game_over(reason)?;
+            return Ok(());
+        }
+
+        let n = self.players.len();
+        let mut next = self.turn;
+        loop {
+            next += 1;
+            if next == n {
+                next = 0;
+                self.end_round();
+            }
+            if self.players[next].is_active() {
+                break;
+            }
+        }
+
+        if let Some(reason) = self.ending() {
+            self.game_over(reason)?;
+            return Ok(());
+        }
+        self.turn = next;
+        self.enter(Phase::StartTurn)?;
+        self.stage = Stage::StartTurn;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        action::{Action, Decision},
+        config::BoardConfig,
+        dice::{RollState, ScriptedDice, SpeedFace},
+        error::MonopolyError,
+        monopoly::MonopolyState,
+        phase::Phase,
+        player::Player,
+        scenario::Scenario,
+        termination::EndConditions,
+    };
+
+    use super::Game;
+
+    #[test]
+    fn game_waits_for_decisions() {
+        let game = Game::new(3, 7);
+        let pending = game.pending().unwrap();
+        assert_eq!(pending.player, game.current_player());
+        assert!(!game.legal_actions().is_empty());
+    }
+
+    #[test]
+    fn illegal_actions_are_rejected() {
+        let mut game = Game::new(2, 11);
+        while game.pending().unwrap().decision != Decision::Manage {
+            let action = game.legal_actions().swap_remove(0);
+            game.apply(action).unwrap();
+        }
+
+        assert!(game.apply(Action::AcceptTrade).is_err());
+        assert!(game.apply(Action::EndTurn).is_ok());
+    }
+
+    #[test]
+    fn bad_ids_and_transitions_are_errors() {
+        let mut game = Game::new(2, 11);
+        assert!(matches!(game.seat(3), Err(MonopolyError::UnknownPlayer(3))));
+        assert!(game.player(3).is_err());
+        assert!(game.net_worth(3).is_err());
+        assert!(game.view().player(3).is_err());
+        assert!(game.view().portfolio(3).is_err());
+        assert!(game.legal_actions_for(3).is_empty());
+        assert!(game.set_turn(0).is_err());
+        assert_eq!(game.phase(), Phase::PreRoll);
+        assert!(matches!(
+            game.enter(Phase::Move),
+            Err(MonopolyError::InvalidTransition(_))
+        ));
+
+        game.state.rules.end = EndConditions::last_standing().with_max_rounds(1);
+        while game.pending().is_some() {
+            game.apply_or_default(Action::EndTurn).unwrap();
+        }
+        assert!(game.is_over());
+        assert!(game.apply_or_default(Action::EndTurn).is_err());
+    }
+
+    #[test]
+    fn declined_property_goes_to_auction() {
+        let mut game = Game::new(2, 3);
+        loop {
+            let pending = game.pending().unwrap();
+            match pending.decision {
+                Decision::Buy { position, .. } => {
+                    game.apply(Action::Decline).unwrap();
+                    let bidder = game.pending().unwrap();
+                    assert!(matches!(bidder.decision, Decision::Bid { highest: 0, .. }));
+
+                    game.apply(Action::Bid(10)).unwrap();
+                    game.apply(Action::Pass).unwrap();
+                    assert_eq!(game.board[position].owner_id(), Some(bidder.player));
+                    break;
+                }
+                _ => {
+                    let action = game.legal_actions().swap_remove(0);
+                    game.apply(action).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn games_are_reproducible_from_a_seed() {
+        let positions = |seed| {
+            let mut game = Game::new(4, seed);
+            for _ in 0..50 {
+                let action = game.legal_actions().pop().unwrap();
+                game.apply(action).unwrap();
+            }
+            game.players
+                .iter()
+                .map(|p| (p.current_position(), p.current_balance()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(positions(42), positions(42));
+    }
+
+    #[test]
+    fn plays_on_a_custom_board() {
+        let mut board = BoardConfig::us();
+        board.squares.truncate(20);
+        let mut game =
+            Game::with_board(Player::create_players(2), &board, MonopolyState::seeded(5));
+        assert_eq!(game.board[1].name(), "Mediterranean Avenue");
+
+        for _ in 0..200 {
+            let action = game.legal_actions().pop().unwrap();
+            game.apply(action).unwrap();
+            assert!(game.players.iter().all(|p| p.current_position() < 20));
+        }
+    }
+
+    #[test]
+    fn three_doubles_send_the_player_to_jail() {
+        let dice = ScriptedDice::from_faces(&[&[2, 2], &[3, 3], &[1, 1]]);
+        let mut game = Game::with_dice(
+            Player::create_players(2),
+            &BoardConfig::uk(),
+            MonopolyState::seeded(1),
+            Box::new(dice),
+        );
+        assert_eq!(game.pending().unwrap().decision, Decision::PreRoll);
+        assert_eq!(game.phase(), Phase::PreRoll);
+        game.apply(Action::Roll).unwrap();
+
+        let player = game.player(1).unwrap();
+        assert!(!player.in_jail());
+        assert_eq!(game.phase(), Phase::Resolve);
+        assert_eq!(
+            game.pending().unwrap().decision,
+            Decision::IncomeTax {
+                flat: 200,
+                percentage: 150
+            }
+        );
+        game.apply(Action::PayFlatTax).unwrap();
+
+        let player = game.player(1).unwrap();
+        assert!(player.in_jail());
+        assert_eq!(player.current_position(), 10);
+        assert_eq!(player.current_balance(), 1300);
+        assert_eq!(game.pending().unwrap().decision, Decision::Manage);
+        assert_eq!(game.phase(), Phase::PostRoll);
+    }
+
+    #[test]
+    fn chance_cards_advance_to_the_boards_own_squares() {
+        // Chance is 7 squares from Go, the fourth card is the station trip
+        let mut game = Scenario::new(2)
+            .board("us")
+            .roll(&[3, 4])
+            .chance(4)
+            .build()
+            .unwrap();
+        game.apply(Action::Roll).unwrap();
+        let player = game.player(1).unwrap();
+        assert_eq!(player.current_position(), 5);
+        assert_eq!(game.board[5].name(), "Reading Railroad");
+        assert_eq!(player.current_balance(), 1700);
+    }
+
+    #[test]
+    fn the_bus_moves_by_either_die_or_both() {
+        let roll = RollState::from_faces(&[1, 2]).with_speed(SpeedFace::Bus);
+        let mut game = Game::with_dice(
+            Player::create_players(2),
+            &BoardConfig::uk(),
+            MonopolyState::seeded(1),
+            Box::new(ScriptedDice::new(vec![roll])),
+        );
+        game.apply(Action::Roll).unwrap();
+
+        assert_eq!(
+            game.pending().unwrap().decision,
+            Decision::Bus { faces: vec![1, 2] }
+        );
+        assert_eq!(
+            game.legal_actions(),
+            [Action::MoveBy(3), Action::MoveBy(1), Action::MoveBy(2)]
+        );
+        assert!(game.apply(Action::MoveBy(4)).is_err());
+        game.apply(Action::MoveBy(1)).unwrap();
+        assert_eq!(game.player(1).unwrap().current_position(), 1);
+        assert!(matches!(
+            game.pending().unwrap().decision,
+            Decision::Buy { position: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn mr_monopoly_moves_on_to_the_next_unowned_property() {
+        let roll = RollState::from_faces(&[1, 2]).with_speed(SpeedFace::MrMonopoly);
+        let mut game = Game::with_dice(
+            Player::create_players(2),
+            &BoardConfig::uk(),
+            MonopolyState::seeded(1),
+            Box::new(ScriptedDice::new(vec![roll])),
+        );
+        game.apply(Action::Roll).unwrap();
+
+        assert_eq!(
+            game.pending().unwrap().decision,
+            Decision::Buy {
+                position: 3,
+                price: 60
+            }
+        );
+        game.apply(Action::Buy).unwrap();
+        assert!(matches!(
+            game.pending().unwrap().decision,
+            Decision::Buy { position: 5, .. }
+        ));
+    }
+
+    #[test]
+    fn legal_actions_follow_the_building_and_trading_rules() {
+        let mut game = Scenario::new(2)
+            .owner(1, 1)
+            .owner(3, 1)
+            .houses(1, 1)
+            .owner(5, 2)
+            .roll(&[2, 4])
+            .build()
+            .unwrap();
+        assert!(game.legal_actions_for(2).is_empty());
+
+        let actions = game.legal_actions_for(1);
+        assert_eq!(actions[0], Action::Roll);
+        assert!(actions.contains(&Action::Build(3)));
+        assert!(!actions.contains(&Action::Build(1)));
+        assert!(actions.contains(&Action::SellBuilding(1)));
+        assert!(!actions.contains(&Action::SellBuilding(3)));
+        let trades: Vec<_> = actions
+            .iter()
+            .filter_map(|a| match a {
+                Action::ProposeTrade(offer) => Some((offer.take.clone(), offer.give_cash)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(trades, [(vec![5], 200)]);
+
+        game.apply(Action::Build(3)).unwrap();
+        assert!(game.can_build(1, 1));
+        game.apply(Action::SellBuilding(1)).unwrap();
+        assert!(game.apply(Action::SellBuilding(1)).is_err());
+        assert_eq!(game.player(1).unwrap().current_balance(), 1475);
+
+        game.apply(Action::Roll).unwrap();
+        game.apply(Action::Decline).unwrap();
+        assert_eq!(game.bid_range(), Some(1..=1475));
+        assert!(game.apply(Action::Bid(1476)).is_err());
+        game.apply(Action::Bid(7)).unwrap();
+        assert_eq!(game.bid_range(), Some(8..=1500));
+    }
+}
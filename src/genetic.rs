@@ -0,0 +1,432 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    batch::{run_batch, BatchConfig},
+    config::BoardConfig,
+    error::MonopolyError,
+    locations::ColourGroup,
+    strategy::{HeuristicAgent, Strategy, StrategyParams},
+};
+
+const GROUPS: usize = ColourGroup::ALL.len();
+const RESERVE: (f64, f64) = (0.0, 1000.0);
+const MAX_PRICE: (f64, f64) = (0.0, 500.0);
+const AGGRESSIVENESS: (f64, f64) = (0.0, 1.0);
+const TRADE_THRESHOLD: (f64, f64) = (0.5, 3.0);
+
+/// Lower and upper bound of every gene, in the order used by `to_genes`
+fn bounds() -> Vec<(f64, f64)> {
+    let mut bounds = vec![RESERVE];
+    bounds.extend(vec![MAX_PRICE; GROUPS]);
+    bounds.push(AGGRESSIVENESS);
+    bounds.push(TRADE_THRESHOLD);
+    bounds
+}
+
+fn to_genes(params: &StrategyParams) -> Vec<f64> {
+    let mut genes = vec![params.reserve as f64];
+    genes.extend(
+        params
+            .max_price
+            .iter()
+            .map(|&p| (p as f64).min(MAX_PRICE.1)),
+    );
+    genes.push(params.build_aggressiveness);
+    genes.push(params.trade_threshold);
+    genes
+}
+
+fn from_genes(genes: &[f64]) -> StrategyParams {
+    let genes: Vec<f64> = genes
+        .iter()
+        .zip(bounds())
+        .map(|(g, (low, high))| g.max(low).min(high))
+        .collect();
+
+    let mut max_price = [0; GROUPS];
+    for (limit, gene) in max_price.iter_mut().zip(&genes[1..=GROUPS]) {
+        *limit = gene.round() as usize;
+    }
+
+    StrategyParams {
+        reserve: genes[0].round() as usize,
+        max_price,
+        build_aggressiveness: genes[GROUPS + 1],
+        trade_threshold: genes[GROUPS + 2],
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Genome {
+    pub params: StrategyParams,
+    /// Mean batch score, 1.0 would be winning every game
+    pub fitness: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Checkpoint {
+    pub generation: usize,
+    pub best: Vec<Genome>,
+}
+
+pub fn save_checkpoint(path: &Path, checkpoint: &Checkpoint) -> Result<(), MonopolyError> {
+    Ok(fs::write(path, serde_json::to_string_pretty(checkpoint)?)?)
+}
+
+pub fn load_checkpoint(path: &Path) -> Result<Checkpoint, MonopolyError> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Who each genome plays against when its fitness is measured
+#[derive(Debug, Clone)]
+pub enum Opponents {
+    /// Other genomes drawn at random from the population, `table_size - 1` per game
+    Population { table_size: usize },
+    /// A fixed line-up of built-in strategies
+    Baselines(Vec<Strategy>),
+}
+
+#[derive(Debug, Clone)]
+pub struct GaConfig {
+    pub population: usize,
+    pub generations: usize,
+    /// Best genomes copied unchanged into the next generation, and checkpointed
+    pub elite: usize,
+    pub tournament: usize,
+    /// Chance of each gene being mutated
+    pub mutation_rate: f64,
+    /// Largest mutation as a share of the gene's range
+    pub mutation_scale: f64,
+    pub opponents: Opponents,
+    pub batch: BatchConfig,
+    /// Board the fitness games are played on
+    pub board: BoardConfig,
+    pub checkpoint: Option<PathBuf>,
+    pub seed: u64,
+}
+
+impl Default for GaConfig {
+    fn default() -> Self {
+        Self {
+            population: 20,
+            generations: 10,
+            elite: 2,
+            tournament: 3,
+            mutation_rate: 0.2,
+            mutation_scale: 0.2,
+            opponents: Opponents::Baselines(vec![Strategy::Greedy, Strategy::Cautious]),
+            batch: BatchConfig {
+                games: 20,
+                max_rounds: 300,
+                seed: 0,
+            },
+            board: BoardConfig::uk(),
+            checkpoint: None,
+            seed: 0,
+        }
+    }
+}
+
+impl GaConfig {
+    /// Settings the optimiser can't run with, e.g. an empty population
+    pub fn validate(&self) -> Result<(), MonopolyError> {
+        let invalid = |reason: String| Err(MonopolyError::InvalidConfig(reason));
+        if self.population == 0 {
+            return invalid("The population needs at least one genome".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.mutation_rate) {
+            return invalid(format!(
+                "Mutation rate {} is not between 0 and 1",
+                self.mutation_rate
+            ));
+        }
+        if !(self.mutation_scale >= 0.0 && self.mutation_scale.is_finite()) {
+            return invalid(format!(
+                "Mutation scale {} is not a positive share of the range",
+                self.mutation_scale
+            ));
+        }
+        if self.batch.games == 0 {
+            return invalid("Fitness needs at least one game per batch".to_string());
+        }
+        match &self.opponents {
+            Opponents::Population { table_size } if *table_size < 2 => invalid(format!(
+                "A table of {} can't play against the population",
+                table_size
+            )),
+            Opponents::Population { table_size } if *table_size > self.population => {
+                invalid(format!(
+                    "A table of {} needs a population of at least as many, not {}",
+                    table_size, self.population
+                ))
+            }
+            Opponents::Baselines(baselines) if baselines.is_empty() => {
+                invalid("No baseline strategies to play against".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Evolves `StrategyParams` by playing each generation in batches of games
+#[derive(Debug)]
+pub struct GeneticOptimiser {
+    config: GaConfig,
+    rng: StdRng,
+    population: Vec<StrategyParams>,
+    generation: usize,
+}
+
+impl GeneticOptimiser {
+    /// Starts from the default parameters plus random genomes
+    pub fn new(config: GaConfig) -> Result<Self, MonopolyError> {
+        config.validate()?;
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let mut population = vec![StrategyParams::default()];
+        while population.len() < config.population {
+            let genes: Vec<f64> = bounds()
+                .into_iter()
+                .map(|(low, high)| rng.gen_range(low..=high))
+                .collect();
+            population.push(from_genes(&genes));
+        }
+
+        Ok(Self {
+            config,
+            rng,
+            population,
+            generation: 0,
+        })
+    }
+
+    /// Carry on from a checkpoint, filling the rest of the population with mutants of its best
+    pub fn resume(config: GaConfig, checkpoint: &Checkpoint) -> Result<Self, MonopolyError> {
+        let mut optimiser = Self::new(config)?;
+        optimiser.generation = checkpoint.generation + 1;
+        let best: Vec<StrategyParams> = checkpoint.best.iter().map(|g| g.params).collect();
+        if !best.is_empty() {
+            for (i, params) in optimiser.population.iter_mut().enumerate() {
+                *params = best[i % best.len()];
+            }
+            for params in optimiser.population.iter_mut().skip(best.len()) {
+                let genes = to_genes(params);
+                *params = from_genes(&Self::mutate(&mut optimiser.rng, &optimiser.config, genes));
+            }
+        }
+        Ok(optimiser)
+    }
+
+    pub const fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Run every remaining generation, returning the final population ranked best first
+    pub fn run(&mut self) -> Result<Vec<Genome>, MonopolyError> {
+        let mut ranked = vec![];
+        while self.generation < self.config.generations {
+            ranked = self.evolve()?;
+        }
+        Ok(ranked)
+    }
+
+    /// Score the current generation, checkpoint its best and breed the next one
+    pub fn evolve(&mut self) -> Result<Vec<Genome>, MonopolyError> {
+        let ranked = self.evaluate();
+        log::info!(
+            "Generation [{}] best fitness {:.3}",
+            self.generation,
+            ranked[0].fitness
+        );
+
+        if let Some(path) = &self.config.checkpoint {
+            let checkpoint = Checkpoint {
+                generation: self.generation,
+                best: ranked
+                    .iter()
+                    .take(self.config.elite.max(1))
+                    .cloned()
+                    .collect(),
+            };
+            save_checkpoint(path, &checkpoint)?;
+        }
+
+        let mut next: Vec<StrategyParams> = ranked
+            .iter()
+            .take(self.config.elite)
+            .map(|g| g.params)
+            .collect();
+        while next.len() < self.config.population {
+            let mother = to_genes(&self.select(&ranked).params);
+            let father = to_genes(&self.select(&ranked).params);
+            let child = mother
+                .iter()
+                .zip(&father)
+                .map(|(&m, &f)| if self.rng.gen_bool(0.5) { m } else { f })
+                .collect();
+            next.push(from_genes(&Self::mutate(
+                &mut self.rng,
+                &self.config,
+                child,
+            )));
+        }
+
+        self.population = next;
+        self.generation += 1;
+        Ok(ranked)
+    }
+
+    fn evaluate(&mut self) -> Vec<Genome> {
+        let mut ranked: Vec<Genome> = (0..self.population.len())
+            .map(|i| {
+                let params = self.population[i];
+                let mut batch = self.config.batch;
+                batch.seed = batch.seed.wrapping_add((self.generation * 1000 + i) as u64);
+
+                let line_up: Vec<Strategy> = match &self.config.opponents {
+                    Opponents::Baselines(baselines) => baselines.clone(),
+                    Opponents::Population { table_size } => {
+                        let others: Vec<StrategyParams> = self
+                            .population
+                            .iter()
+                            .enumerate()
+                            .filter(|(j, _)| *j != i)
+                            .map(|(_, p)| *p)
+                            .collect();
+                        others
+                            .choose_multiple(&mut self.rng, table_size.saturating_sub(1))
+                            .map(|p| Strategy::Tuned(*p))
+                            .collect()
+                    }
+                };
+
+                let result = run_batch(
+                    &self.config.board,
+                    line_up.len() + 1,
+                    batch,
+                    |entrant, seed| {
+                        if entrant == 0 {
+                            Box::new(HeuristicAgent::with_params(params))
+                        } else {
+                            line_up[entrant - 1].agent(seed)
+                        }
+                    },
+                );
+
+                Genome {
+                    params,
+                    fitness: result.mean_score(0),
+                }
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+        ranked
+    }
+
+    /// Tournament selection
+    fn select<'a>(&mut self, ranked: &'a [Genome]) -> &'a Genome {
+        (0..self.config.tournament.max(1))
+            .map(|_| &ranked[self.rng.gen_range(0..ranked.len())])
+            .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+            .unwrap_or(&ranked[0])
+    }
+
+    fn mutate(rng: &mut StdRng, config: &GaConfig, genes: Vec<f64>) -> Vec<f64> {
+        genes
+            .into_iter()
+            .zip(bounds())
+            .map(|(gene, (low, high))| {
+                if rng.gen_bool(config.mutation_rate) {
+                    let step = (high - low) * config.mutation_scale;
+                    gene + rng.gen_range(-step..=step)
+                } else {
+                    gene
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{batch::BatchConfig, error::MonopolyError, strategy::StrategyParams};
+
+    use super::{from_genes, load_checkpoint, to_genes, GaConfig, GeneticOptimiser, Opponents};
+
+    #[test]
+    fn genes_round_trip() {
+        let params = StrategyParams {
+            reserve: 250,
+            max_price: [120; 10],
+            build_aggressiveness: 0.5,
+            trade_threshold: 2.0,
+        };
+        assert_eq!(from_genes(&to_genes(&params)), params);
+    }
+
+    #[test]
+    fn evolves_and_checkpoints_the_best() {
+        let dir = std::env::temp_dir().join(format!("monopoly_abm_ga_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("checkpoint.json");
+        let config = GaConfig {
+            population: 4,
+            generations: 2,
+            elite: 1,
+            opponents: Opponents::Population { table_size: 3 },
+            batch: BatchConfig {
+                games: 2,
+                max_rounds: 50,
+                seed: 1,
+            },
+            checkpoint: Some(path.clone()),
+            ..GaConfig::default()
+        };
+
+        let mut optimiser = GeneticOptimiser::new(config.clone()).unwrap();
+        let ranked = optimiser.run().unwrap();
+        assert_eq!(ranked.len(), 4);
+        assert!(ranked.windows(2).all(|w| w[0].fitness >= w[1].fitness));
+
+        let checkpoint = load_checkpoint(&path).unwrap();
+        assert_eq!(checkpoint.generation, 1);
+        assert_eq!(checkpoint.best[0].params, ranked[0].params);
+
+        let resumed = GeneticOptimiser::resume(config, &checkpoint).unwrap();
+        assert_eq!(resumed.generation(), 2);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unusable_configs_are_rejected() {
+        for config in [
+            GaConfig {
+                population: 0,
+                ..GaConfig::default()
+            },
+            GaConfig {
+                mutation_rate: 1.5,
+                ..GaConfig::default()
+            },
+            GaConfig {
+                opponents: Opponents::Population { table_size: 1 },
+                ..GaConfig::default()
+            },
+            GaConfig {
+                opponents: Opponents::Baselines(vec![]),
+                ..GaConfig::default()
+            },
+        ] {
+            assert!(matches!(
+                GeneticOptimiser::new(config),
+                Err(MonopolyError::InvalidConfig(_))
+            ));
+        }
+    }
+}
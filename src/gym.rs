@@ -0,0 +1,419 @@
+use serde::Deserialize;
+
+use crate::{
+    action::{Action, Decision},
+    agent::Agent,
+    error::MonopolyError,
+    game::{Game, BID_INCREMENTS},
+    observation::Encoder,
+    player::{Player, PlayerId},
+    strategy::Strategy,
+};
+
+const BID_OFFSET: usize = 3;
+const BUS_OFFSET: usize = BID_OFFSET + BID_INCREMENTS.len() + 9;
+const FIXED_ACTIONS: usize = BUS_OFFSET + 3;
+
+/// How the learner is rewarded after each step
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum Reward {
+    /// +1 for winning, -1 for going bankrupt, 0 otherwise
+    WinLoss,
+    /// Change in net worth since the previous step
+    NetWorthDelta,
+    /// +1 for every round survived, -1 for going bankrupt
+    Survival,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnvConfig {
+    pub opponents: Vec<Strategy>,
+    pub learner_seat: usize,
+    pub reward: Reward,
+    pub max_rounds: usize,
+}
+
+impl Default for EnvConfig {
+    fn default() -> Self {
+        Self {
+            opponents: vec![Strategy::Greedy; 3],
+            learner_seat: 0,
+            reward: Reward::WinLoss,
+            max_rounds: 200,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Observation {
+    /// See `Encoder` for the feature layout
+    pub decision: Option<Decision>,
+    pub features: Vec<f32>,
+    pub action_mask: Vec<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StepInfo {
+    pub illegal_action: bool,
+    pub truncated: bool,
+    pub rounds: usize,
+    pub winner: Option<PlayerId>,
+}
+
+/// Fixed, discrete numbering of the actions so they can be masked:
+///
+/// | index                | action                                   |
+/// |----------------------|------------------------------------------|
+/// | 0, 1, 2              | Buy, Decline, Pass                       |
+/// | 3..7                 | Bid, raising by each of `BID_INCREMENTS` |
+/// | 7..16                | PayBail, UseJailCard, RollForDoubles,    |
+/// |                      | AcceptTrade, RejectTrade, EndTurn,       |
+/// |                      | PayFlatTax, PayTaxPercentage, Roll       |
+/// | 16..19               | MoveBy both dice, the first, the second  |
+/// | 19.. (one per square)| Build, Mortgage, Unmortgage, SellBuilding|
+#[derive(Debug, Clone, Copy)]
+pub struct ActionSpace {
+    board_size: usize,
+}
+
+impl ActionSpace {
+    pub const fn new(board_size: usize) -> Self {
+        Self { board_size }
+    }
+
+    pub const fn size(&self) -> usize {
+        FIXED_ACTIONS + 4 * self.board_size
+    }
+
+    pub fn action(&self, index: usize, decision: &Decision) -> Option<Action> {
+        let fixed = [
+            Action::PayBail,
+            Action::UseJailCard,
+            Action::RollForDoubles,
+            Action::AcceptTrade,
+            Action::RejectTrade,
+            Action::EndTurn,
+            Action::PayFlatTax,
+            Action::PayTaxPercentage,
+            Action::Roll,
+        ];
+        let bids = BID_OFFSET + BID_INCREMENTS.len();
+
+        let action = match index {
+            0 => Action::Buy,
+            1 => Action::Decline,
+            2 => Action::Pass,
+            i if i < bids => match decision {
+                Decision::Bid { highest, .. } => {
+                    Action::Bid(highest + BID_INCREMENTS[i - BID_OFFSET])
+                }
+                _ => return None,
+            },
+            i if i < BUS_OFFSET => fixed[i - bids].clone(),
+            i if i < FIXED_ACTIONS => match decision {
+                Decision::Bus { faces } if i == BUS_OFFSET => Action::MoveBy(faces.iter().sum()),
+                Decision::Bus { faces } => Action::MoveBy(*faces.get(i - BUS_OFFSET - 1)?),
+                _ => return None,
+            },
+            i if i < self.size() => {
+                let position = (i - FIXED_ACTIONS) % self.board_size;
+                match (i - FIXED_ACTIONS) / self.board_size {
+                    0 => Action::Build(position),
+                    1 => Action::Mortgage(position),
+                    2 => Action::Unmortgage(position),
+                    _ => Action::SellBuilding(position),
+                }
+            }
+            _ => return None,
+        };
+
+        Some(action)
+    }
+
+    pub fn index(&self, action: &Action, decision: &Decision) -> Option<usize> {
+        (0..self.size()).find(|&i| self.action(i, decision).as_ref() == Some(action))
+    }
+
+    pub fn mask(&self, game: &Game) -> Vec<bool> {
+        let mut mask = vec![false; self.size()];
+        if let Some(pending) = game.pending() {
+            for action in game.legal_actions() {
+                if let Some(i) = self.index(&action, &pending.decision) {
+                    mask[i] = true;
+                }
+            }
+        }
+        mask
+    }
+}
+
+/// Reinforcement learning environment: the learner takes one seat and the
+/// remaining seats are played by built-in strategies between its decisions.
+pub struct MonopolyEnv {
+    config: EnvConfig,
+    game: Game,
+    opponents: Vec<Option<Box<dyn Agent>>>,
+    space: ActionSpace,
+    encoder: Encoder,
+    learner: PlayerId,
+    net_worth: usize,
+    rounds: usize,
+}
+
+impl MonopolyEnv {
+    pub fn new(config: EnvConfig) -> Result<Self, MonopolyError> {
+        if config.learner_seat > config.opponents.len() {
+            return Err(MonopolyError::InvalidConfig(format!(
+                "Seat {} does not exist with {} players (seats start at 0)",
+                config.learner_seat,
+                config.opponents.len() + 1
+            )));
+        }
+        let game = Game::new(config.opponents.len() + 1, 0);
+        let mut env = Self {
+            space: ActionSpace::new(game.board.len()),
+            encoder: Encoder::new(game.board.len()),
+            learner: game.players[config.learner_seat].id,
+            config,
+            game,
+            opponents: vec![],
+            net_worth: 0,
+            rounds: 0,
+        };
+        env.reset(0);
+        Ok(env)
+    }
+
+    pub const fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub const fn action_space(&self) -> ActionSpace {
+        self.space
+    }
+
+    pub const fn learner(&self) -> PlayerId {
+        self.learner
+    }
+
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        let n_players = self.config.opponents.len() + 1;
+        self.game = Game::new(n_players, seed);
+
+        let mut strategies = self.config.opponents.iter();
+        self.opponents = (0..n_players)
+            .map(|seat| {
+                if seat == self.config.learner_seat {
+                    None
+                } else {
+                    strategies
+                        .next()
+                        .map(|s| s.agent(seed.wrapping_add(seat as u64 + 1)))
+                }
+            })
+            .collect();
+
+        self.run_opponents();
+        self.net_worth = self.learner_net_worth();
+        self.rounds = self.game.rounds();
+        self.observe()
+    }
+
+    /// Take the action numbered by `ActionSpace`. Illegal actions leave the game untouched.
+    pub fn step(&mut self, action: usize) -> (Observation, f64, bool, StepInfo) {
+        let mut info = StepInfo::default();
+        let chosen = self
+            .game
+            .pending()
+            .filter(|pending| pending.player == self.learner)
+            .and_then(|pending| self.space.action(action, &pending.decision));
+
+        match chosen.map(|a| self.game.apply(a)) {
+            Some(Ok(())) => self.run_opponents(),
+            _ => info.illegal_action = true,
+        }
+
+        let done = self.is_done();
+        let reward = self.reward(done);
+        info.rounds = self.game.rounds();
+        info.winner = self.game.winner();
+        info.truncated = done && !self.game.is_over() && self.learner_active();
+
+        (self.observe(), reward, done, info)
+    }
+
+    /// The learner keeps its seat for the whole episode
+    fn learner_player(&self) -> &Player {
+        &self.game.players[self.config.learner_seat]
+    }
+
+    fn learner_active(&self) -> bool {
+        self.learner_player().is_active()
+    }
+
+    fn learner_net_worth(&self) -> usize {
+        self.game.holdings(self.learner_player()).net_worth()
+    }
+
+    fn is_done(&self) -> bool {
+        self.game.is_over()
+            || !self.learner_active()
+            || self.game.rounds() >= self.config.max_rounds
+    }
+
+    /// Let the built-in strategies play until the learner has to decide
+    fn run_opponents(&mut self) {
+        while let Some(pending) = self.game.pending() {
+            if pending.player == self.learner || self.is_done() {
+                break;
+            }
+
+            let opponents = &mut self.opponents;
+            let seat = self.game.seat(pending.player).ok();
+            let action = match seat.and_then(|seat| opponents[seat].as_mut()) {
+                Some(agent) => agent.decide(&self.game.view(), &pending),
+                None => Action::EndTurn,
+            };
+            if let Err(e) = self.game.apply_or_default(action) {
+                log::error!("{} [Player={}]", e, pending.player);
+                break;
+            }
+        }
+    }
+
+    fn reward(&mut self, done: bool) -> f64 {
+        let net_worth = self.learner_net_worth();
+        let rounds = self.game.rounds();
+        let reward = match self.config.reward {
+            Reward::WinLoss if done && self.game.winner() == Some(self.learner) => 1.0,
+            Reward::WinLoss | Reward::Survival if !self.learner_active() => -1.0,
+            Reward::WinLoss => 0.0,
+            Reward::NetWorthDelta => net_worth as f64 - self.net_worth as f64,
+            Reward::Survival => (rounds - self.rounds) as f64,
+        };
+
+        self.net_worth = net_worth;
+        self.rounds = rounds;
+        reward
+    }
+
+    fn observe(&self) -> Observation {
+        let features = self.encoder.encode_game(&self.game, self.learner);
+        let decision = self
+            .game
+            .pending()
+            .filter(|pending| pending.player == self.learner)
+            .map(|pending| pending.decision);
+        let action_mask = if decision.is_some() {
+            self.space.mask(&self.game)
+        } else {
+            vec![false; self.space.size()]
+        };
+
+        Observation {
+            decision,
+            features,
+            action_mask,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        action::{Action, Decision},
+        error::MonopolyError,
+    };
+
+    use super::{ActionSpace, EnvConfig, MonopolyEnv, Reward};
+
+    fn run_episode(env: &mut MonopolyEnv, seed: u64) -> (usize, f64) {
+        let mut observation = env.reset(seed);
+        let (mut steps, mut total) = (0, 0.0);
+        loop {
+            let action = observation
+                .action_mask
+                .iter()
+                .rposition(|&legal| legal)
+                .unwrap();
+            let (next, reward, done, info) = env.step(action);
+            assert!(!info.illegal_action);
+
+            steps += 1;
+            total += reward;
+            observation = next;
+            if done {
+                return (steps, total);
+            }
+        }
+    }
+
+    #[test]
+    fn episodes_run_to_completion() {
+        let mut env = MonopolyEnv::new(EnvConfig {
+            max_rounds: 50,
+            ..EnvConfig::default()
+        })
+        .unwrap();
+
+        let observation = env.reset(1);
+        assert_eq!(observation.action_mask.len(), env.action_space().size());
+        assert!(observation.decision.is_some());
+
+        let (steps, total) = run_episode(&mut env, 1);
+        assert!(steps > 0);
+        assert!((-1.0..=1.0).contains(&total));
+    }
+
+    #[test]
+    fn same_seed_same_episode() {
+        let mut env = MonopolyEnv::new(EnvConfig {
+            reward: Reward::NetWorthDelta,
+            max_rounds: 30,
+            ..EnvConfig::default()
+        })
+        .unwrap();
+
+        assert_eq!(run_episode(&mut env, 9), run_episode(&mut env, 9));
+    }
+
+    #[test]
+    fn illegal_actions_are_flagged() {
+        let mut env = MonopolyEnv::new(EnvConfig::default()).unwrap();
+        let observation = env.reset(2);
+        let illegal = observation.action_mask.iter().position(|&l| !l).unwrap();
+
+        let (_, reward, _, info) = env.step(illegal);
+        assert!(info.illegal_action);
+        assert_eq!(reward, 0.0);
+    }
+
+    #[test]
+    fn bus_moves_have_indices() {
+        let space = ActionSpace::new(40);
+        let decision = Decision::Bus { faces: vec![4, 1] };
+        let moves: Vec<_> = (16..19).map(|i| space.action(i, &decision)).collect();
+        assert_eq!(
+            moves,
+            [
+                Some(Action::MoveBy(5)),
+                Some(Action::MoveBy(4)),
+                Some(Action::MoveBy(1))
+            ]
+        );
+        assert_eq!(space.action(16, &Decision::Manage), None);
+        assert_eq!(space.action(19, &decision), Some(Action::Build(0)));
+    }
+
+    #[test]
+    fn learner_must_have_a_seat() {
+        let config = EnvConfig {
+            learner_seat: 4,
+            ..EnvConfig::default()
+        };
+        assert!(matches!(
+            MonopolyEnv::new(config),
+            Err(MonopolyError::InvalidConfig(_))
+        ));
+    }
+}
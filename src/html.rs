@@ -0,0 +1,92 @@
+use serde_json::{json, Value};
+
+use crate::{
+    error::MonopolyError,
+    replay::{ring_cell, ring_side, Replay},
+    svg::{group_colour, PLAYER_COLOURS},
+};
+
+/// Page with the board, scrubber, cash chart and event list, filled in by `page`
+const TEMPLATE: &str = include_str!("viewer.html");
+
+/// The replay as one self-contained HTML page that plays the game back turn by turn,
+/// with everything it needs inlined so it opens offline
+pub fn page(replay: &Replay) -> Result<String, MonopolyError> {
+    let n = replay.squares.len();
+    let mut data = serde_json::to_value(replay)?;
+    data["across"] = json!(ring_side(n) + 1);
+
+    for (position, (square, sq)) in replay
+        .squares
+        .iter()
+        .zip(cells(&mut data["squares"]))
+        .enumerate()
+    {
+        let (col, row) = ring_cell(position, n);
+        sq["col"] = json!(col);
+        sq["row"] = json!(row);
+        sq["colour"] = json!(square.group.map(group_colour));
+    }
+    for (seat, player) in cells(&mut data["players"]).enumerate() {
+        player["colour"] = json!(PLAYER_COLOURS[seat % PLAYER_COLOURS.len()]);
+    }
+    // Events go out already described so the page doesn't need to know them
+    for (frame, f) in replay.frames.iter().zip(cells(&mut data["frames"])) {
+        f["events"] = frame.events.iter().map(|e| replay.describe(e)).collect();
+    }
+
+    let names: Vec<&str> = replay.players.iter().map(|p| p.name.as_str()).collect();
+    let title = format!("Monopoly: {}", names.join(" v "));
+    Ok(TEMPLATE
+        .replace("__TITLE__", &escape(&title))
+        .replace("__DATA__", &script_safe(&data.to_string())))
+}
+
+fn cells(value: &mut Value) -> impl Iterator<Item = &mut Value> {
+    value.as_array_mut().into_iter().flatten()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// JSON that can't close the `<script>` it sits in. A `<` can only turn up inside
+/// a string, where `\u003c` reads the same.
+fn script_safe(json: &str) -> String {
+    json.replace('<', r"\u003c")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{game::Game, replay::Replay, strategy::Strategy};
+
+    use super::{page, script_safe};
+
+    #[test]
+    fn page_embeds_every_frame_and_stays_offline() {
+        let mut game = Game::new(2, 7);
+        let mut agents: Vec<_> = (0..2).map(|s| Strategy::Greedy.agent(s)).collect();
+        let mut replay = Replay::record(&mut game, &mut agents, 5);
+        replay.players[0].name = "</script><b>".to_string();
+
+        let html = page(&replay).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert!(html.contains(r#""across":11"#));
+        assert_eq!(
+            html.matches(r#""free_parking""#).count(),
+            replay.frames.len()
+        );
+        assert!(!html.contains("src="));
+        assert!(!html.contains("href="));
+        assert!(!html.contains("https://"));
+
+        let json = r#"{"name":"</script><!-- <b>"}"#;
+        let safe = script_safe(json);
+        assert!(!safe.contains('<'));
+        let parsed: serde_json::Value = serde_json::from_str(&safe).unwrap();
+        assert_eq!(parsed["name"], "</script><!-- <b>");
+    }
+}
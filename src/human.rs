@@ -0,0 +1,263 @@
+use std::io::{self, BufRead, BufReader, Stdin, Stdout, Write};
+
+use crate::{
+    action::{Action, Decision, PendingDecision, TradeOffer},
+    agent::Agent,
+    player::{PlayerId, JAIL_BAIL},
+    view::GameView,
+};
+
+/// A seat played from a terminal. Shows the table, lists every legal action
+/// and keeps asking until one of them is picked.
+pub struct HumanAgent<R, W> {
+    input: R,
+    output: W,
+}
+
+impl HumanAgent<BufReader<Stdin>, Stdout> {
+    pub fn stdio() -> Self {
+        Self::new(BufReader::new(io::stdin()), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> HumanAgent<R, W> {
+    pub const fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+
+    /// Index into `actions`, or `None` once the input has run out
+    fn ask(
+        &mut self,
+        view: &GameView<'_>,
+        pending: &PendingDecision,
+        actions: &[Action],
+    ) -> io::Result<Option<usize>> {
+        if matches!(pending.decision, Decision::PreRoll | Decision::Jail) {
+            self.show_board(view)?;
+        }
+        self.show_player(view, pending.player)?;
+        writeln!(
+            self.output,
+            "{}",
+            describe_decision(view, &pending.decision)
+        )?;
+        for (i, action) in actions.iter().enumerate() {
+            writeln!(self.output, "  {:>2}) {}", i + 1, describe(view, action))?;
+        }
+
+        loop {
+            write!(self.output, "> ")?;
+            self.output.flush()?;
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            match line.trim().parse::<usize>() {
+                Ok(n) if (1..=actions.len()).contains(&n) => return Ok(Some(n - 1)),
+                _ => writeln!(self.output, "Pick a number from 1 to {}", actions.len())?,
+            }
+        }
+    }
+
+    fn show_board(&mut self, view: &GameView<'_>) -> io::Result<()> {
+        writeln!(self.output)?;
+        for sq in view.squares() {
+            let owner = sq
+                .owner
+                .map_or(String::new(), |id| player_name(view, id).to_string());
+            let buildings = match (sq.houses, sq.hotels) {
+                (_, 0) => "*".repeat(sq.houses),
+                _ => "H".to_string(),
+            };
+            let tokens: Vec<&str> = view
+                .players()
+                .filter(|p| p.active && p.position == sq.position)
+                .map(|p| p.name)
+                .collect();
+            writeln!(
+                self.output,
+                "{:>2} {:<28} {:<10} {:<5}{} {}",
+                sq.position,
+                sq.name,
+                owner,
+                buildings,
+                if sq.mortgaged { "M" } else { " " },
+                tokens.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+
+    fn show_player(&mut self, view: &GameView<'_>, id: PlayerId) -> io::Result<()> {
+        let (player, portfolio) = view
+            .player(id)
+            .and_then(|player| Ok((player, view.portfolio(id)?)))
+            .map_err(io::Error::other)?;
+        writeln!(
+            self.output,
+            "\n{} on {} with {} cash, net worth {}",
+            player.name,
+            view.square(player.position).name,
+            player.cash,
+            portfolio.net_worth()
+        )?;
+        for holding in &portfolio.holdings {
+            writeln!(
+                self.output,
+                "    {}{}{}",
+                holding.name,
+                match (holding.houses, holding.hotels) {
+                    (0, 0) => String::new(),
+                    (houses, 0) => format!(", {} houses", houses),
+                    _ => ", hotel".to_string(),
+                },
+                if holding.mortgaged { ", mortgaged" } else { "" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: BufRead, W: Write> Agent for HumanAgent<R, W> {
+    fn decide(&mut self, view: &GameView<'_>, pending: &PendingDecision) -> Action {
+        let mut actions = view.legal_actions();
+        match self.ask(view, pending, &actions) {
+            Ok(Some(i)) => actions.swap_remove(i),
+            Ok(None) | Err(_) => {
+                log::warn!("No input, taking the default [Player={}]", pending.player);
+                actions.swap_remove(0)
+            }
+        }
+    }
+}
+
+fn describe_decision(view: &GameView<'_>, decision: &Decision) -> String {
+    match decision {
+        Decision::PreRoll => "Before rolling:".to_string(),
+        Decision::Jail => "In jail:".to_string(),
+        Decision::Buy { position, price } => {
+            format!("Buy {} for {}?", view.square(*position).name, price)
+        }
+        Decision::Bid { position, highest } => format!(
+            "Auction for {}, highest bid {}:",
+            view.square(*position).name,
+            highest
+        ),
+        Decision::Manage => "Build, mortgage or trade before ending the turn:".to_string(),
+        Decision::TradeResponse { offer } => format!(
+            "{} offers: {}",
+            player_name(view, offer.from),
+            describe_trade(view, offer)
+        ),
+        Decision::IncomeTax { flat, percentage } => {
+            format!(
+                "Income Tax, pay {} flat or {} on your net worth:",
+                flat, percentage
+            )
+        }
+        Decision::Bus { faces } => format!("Bus, rolled {:?}:", faces),
+    }
+}
+
+fn player_name<'a>(view: &GameView<'a>, id: PlayerId) -> &'a str {
+    view.player(id).map_or("an unknown player", |p| p.name)
+}
+
+fn describe(view: &GameView<'_>, action: &Action) -> String {
+    let name = |position| view.square(position).name;
+    match action {
+        Action::Buy => "Buy".to_string(),
+        Action::Decline => "Decline, it goes to auction".to_string(),
+        Action::Bid(bid) => format!("Bid {}", bid),
+        Action::Pass => "Pass".to_string(),
+        Action::Build(p) => format!("Build on {} for {}", name(*p), view.square(*p).upgrade_cost),
+        Action::SellBuilding(p) => format!("Sell a building on {}", name(*p)),
+        Action::Mortgage(p) => {
+            format!(
+                "Mortgage {} for {}",
+                name(*p),
+                view.square(*p).mortgage_value
+            )
+        }
+        Action::Unmortgage(p) => format!(
+            "Lift the mortgage on {} for {}",
+            name(*p),
+            view.square(*p).unmortgage_cost
+        ),
+        Action::ProposeTrade(offer) => format!(
+            "Offer {}: {}",
+            player_name(view, offer.to),
+            describe_trade(view, offer)
+        ),
+        Action::EndTurn => "End turn".to_string(),
+        Action::PayBail => format!("Pay {} bail", JAIL_BAIL),
+        Action::UseJailCard => "Use Get Out of Jail Free".to_string(),
+        Action::RollForDoubles => "Roll for doubles".to_string(),
+        Action::AcceptTrade => "Accept".to_string(),
+        Action::RejectTrade => "Reject".to_string(),
+        Action::Roll => "Roll".to_string(),
+        Action::PayFlatTax => "Pay the flat amount".to_string(),
+        Action::PayTaxPercentage => "Pay the percentage".to_string(),
+        Action::MoveBy(steps) => format!("Move {} squares", steps),
+    }
+}
+
+/// "Old Kent Road + 50 for Mayfair"
+fn describe_trade(view: &GameView<'_>, offer: &TradeOffer) -> String {
+    let side = |squares: &[usize], cash: usize| {
+        let mut parts: Vec<String> = squares
+            .iter()
+            .map(|&p| view.square(p).name.to_string())
+            .collect();
+        if cash > 0 {
+            parts.push(cash.to_string());
+        }
+        if parts.is_empty() {
+            "nothing".to_string()
+        } else {
+            parts.join(" + ")
+        }
+    };
+    format!(
+        "{} for {}",
+        side(&offer.give, offer.give_cash),
+        side(&offer.take, offer.take_cash)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::{action::Action, agent::Agent, scenario::Scenario};
+
+    use super::HumanAgent;
+
+    #[test]
+    fn input_is_checked_against_the_legal_actions() {
+        // Whitechapel Road is for sale after a roll of 3
+        let mut game = Scenario::new(2).roll(&[1, 2]).build().unwrap();
+        let mut output = vec![];
+        let mut human = HumanAgent::new(Cursor::new("roll\n0\n1\n2\n"), &mut output);
+
+        let pending = game.pending().unwrap();
+        let action = human.decide(&game.view(), &pending);
+        assert_eq!(action, Action::Roll);
+        game.apply(action).unwrap();
+
+        let pending = game.pending().unwrap();
+        assert_eq!(human.decide(&game.view(), &pending), Action::Buy);
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("Pick a number from 1 to").count(), 2);
+        assert!(output.contains("Buy Whitechapel Road for 60?"));
+    }
+
+    #[test]
+    fn running_out_of_input_takes_the_default() {
+        let game = Scenario::new(2).build().unwrap();
+        let mut human = HumanAgent::new(Cursor::new(""), vec![]);
+        let pending = game.pending().unwrap();
+        assert_eq!(human.decide(&game.view(), &pending), Action::Roll);
+    }
+}
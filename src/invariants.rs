@@ -0,0 +1,169 @@
+use std::fmt;
+
+use crate::{game::Game, player::PlayerId, square::BoardPosition};
+
+/// Something about the game state that can't happen under the rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// Cash held by the players and the pot doesn't match the bank's records
+    CashNotConserved {
+        expected: usize,
+        found: usize,
+    },
+    UnknownOwner {
+        position: BoardPosition,
+        owner: PlayerId,
+    },
+    OwnedByBankruptPlayer {
+        position: BoardPosition,
+        owner: PlayerId,
+    },
+    OwnedButNotOwnable {
+        position: BoardPosition,
+    },
+    BuildingsWithoutOwner {
+        position: BoardPosition,
+    },
+    BuildingsOnMortgage {
+        position: BoardPosition,
+    },
+    BuildingsNotAllowed {
+        position: BoardPosition,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CashNotConserved { expected, found } => write!(
+                f,
+                "Players and pot hold {} but the bank's records say {}",
+                found, expected
+            ),
+            Self::UnknownOwner { position, owner } => {
+                write!(
+                    f,
+                    "Square {} is owned by unknown player {}",
+                    position, owner
+                )
+            }
+            Self::OwnedByBankruptPlayer { position, owner } => write!(
+                f,
+                "Square {} is still owned by bankrupt player {}",
+                position, owner
+            ),
+            Self::OwnedButNotOwnable { position } => {
+                write!(f, "Square {} can't be owned but has an owner", position)
+            }
+            Self::BuildingsWithoutOwner { position } => {
+                write!(f, "Square {} has buildings but no owner", position)
+            }
+            Self::BuildingsOnMortgage { position } => {
+                write!(f, "Square {} is mortgaged with buildings on it", position)
+            }
+            Self::BuildingsNotAllowed { position } => {
+                write!(f, "Square {} has buildings but can't be built on", position)
+            }
+        }
+    }
+}
+
+/// Every violation found in one check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violations(pub Vec<Violation>);
+
+impl fmt::Display for Violations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", lines.join("; "))
+    }
+}
+
+impl std::error::Error for Violations {}
+
+/// Cash is conserved against the bank's records, every owner is a player still
+/// in the game and buildings only stand where they are allowed
+pub fn check(game: &Game) -> Result<(), Violations> {
+    let mut violations = vec![];
+
+    let expected = game.state.bank.expected_cash();
+    let found = game.total_cash();
+    if expected != found {
+        violations.push(Violation::CashNotConserved { expected, found });
+    }
+
+    for (position, sq) in game.board.iter().enumerate() {
+        let s = sq.property_state();
+        let buildings = s.house_count + s.hotel_count > 0;
+        match s.owner {
+            Some(_) if !sq.is_ownable() => {
+                violations.push(Violation::OwnedButNotOwnable { position })
+            }
+            Some(owner) => match game.players.iter().find(|p| p.id == owner) {
+                None => violations.push(Violation::UnknownOwner { position, owner }),
+                Some(p) if !p.is_active() => {
+                    violations.push(Violation::OwnedByBankruptPlayer { position, owner })
+                }
+                Some(_) => {}
+            },
+            None if buildings => violations.push(Violation::BuildingsWithoutOwner { position }),
+            None => {}
+        }
+        if buildings && s.mortgaged {
+            violations.push(Violation::BuildingsOnMortgage { position });
+        }
+        if buildings && !sq.colour_group().is_some_and(|g| g.is_buildable()) {
+            violations.push(Violation::BuildingsNotAllowed { position });
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(Violations(violations))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{game::Game, scenario::Scenario, square::PropertyState, strategy::Strategy};
+
+    use super::{check, Violation};
+
+    #[test]
+    fn full_games_keep_every_invariant() {
+        for seed in 0..10 {
+            let mut game = Game::new(4, seed);
+            game.check_invariants(true);
+            let mut agents: Vec<_> = [
+                Strategy::Greedy,
+                Strategy::Random,
+                Strategy::Cautious,
+                Strategy::Greedy,
+            ]
+            .iter()
+            .map(|s| s.agent(seed))
+            .collect();
+            game.play(&mut agents, 300);
+            assert_eq!(check(&game), Ok(()));
+        }
+    }
+
+    #[test]
+    fn violations_are_reported() {
+        let game = Scenario::new(2).owner(1, 2).houses(1, 2).build().unwrap();
+        assert_eq!(check(&game), Ok(()));
+
+        game.player(1).unwrap().deposit(10);
+        game.board[1].set_property_state(PropertyState {
+            mortgaged: true,
+            ..game.board[1].property_state()
+        });
+        let violations = check(&game).unwrap_err().0;
+        assert!(violations.contains(&Violation::CashNotConserved {
+            expected: 3000,
+            found: 3010
+        }));
+        assert!(violations.contains(&Violation::BuildingsOnMortgage { position: 1 }));
+    }
+}
@@ -2,13 +2,46 @@
 // #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
 #![allow(dead_code)]
 
-mod agent;
-mod config;
-mod dice;
-mod locations;
+pub mod action;
+pub mod agent;
+pub mod batch;
+pub mod config;
+pub mod dice;
+pub mod error;
+pub mod event;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod external;
+pub mod game;
+pub mod genetic;
+pub mod gym;
+pub mod html;
+pub mod human;
+pub mod invariants;
+pub mod locations;
+pub mod mcts;
 pub mod monopoly;
+pub mod observation;
 mod payment;
+pub mod phase;
 pub mod player;
-mod square;
+pub mod portfolio;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod replay;
+pub mod roster;
+pub mod rules;
+pub mod scenario;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod server;
+pub mod square;
 mod state;
-mod token;
+pub mod stats;
+pub mod strategy;
+pub mod svg;
+pub mod termination;
+pub mod token;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod view;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -1,15 +1,19 @@
-use num_enum::IntoPrimitive;
-use serde::Deserialize;
+use std::convert::TryFrom;
 
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// Squares of the standard UK board, in board order
 #[allow(dead_code)]
-#[derive(Debug, Deserialize, PartialEq, Hash, IntoPrimitive, Clone, Copy)]
+#[derive(Debug, Deserialize, PartialEq, Hash, IntoPrimitive, TryFromPrimitive, Clone, Copy)]
 #[repr(u16)]
 pub enum BoardLocation {
     Go,
     OldKentRoad,
+    CommunityChest1,
     WhitechapelRoad,
     IncomeTax,
-    MaryleboneStation,
+    KingCrossStation,
     TheAngelIslington,
     Chance1,
     EustonRoad,
@@ -19,9 +23,9 @@ pub enum BoardLocation {
     ElectricCompany,
     Whitehall,
     NorthumberlandAvenue,
-    FenchurchStreetStation,
+    MaryleboneStation,
     BowStreet,
-    CommunityChest1,
+    CommunityChest2,
     GreatMarlboroughStreet,
     VineStreet,
     FreeParking,
@@ -29,7 +33,7 @@ pub enum BoardLocation {
     Chance2,
     FleetStreet,
     TrafalgarSquare,
-    KingCrossStation,
+    FenchurchStreetStation,
     LeicesterSquare,
     CoventryStreet,
     WaterCompany,
@@ -37,7 +41,7 @@ pub enum BoardLocation {
     GoToJail,
     RegentStreet,
     OxfordStreet,
-    CommunityChest2,
+    CommunityChest3,
     BondStreet,
     LiverpoolStreetStation,
     Chance3,
@@ -48,68 +52,200 @@ pub enum BoardLocation {
 
 impl Eq for BoardLocation {}
 
-// impl BoardLocationName {
-//     #[allow(dead_code)]
-//     fn position(position: usize) -> Self {
-//         match position {
-//             0 => Self::Go,
-//             1 => Self::OldKentRoad,
-//             2 => Self::WhitechapelRoad,
-//             3 => Self::IncomeTax,
-//             4 => Self::MaryleboneStation,
-//             5 => Self::TheAngelIslington,
-//             6 => Self::Chance1,
-//             7 => Self::EustonRoad,
-//             8 => Self::PentonvilleRoad,
-//             9 => Self::Jail,
-//             10 => Self::PallMall,
-//             11 => Self::ElectricCompany,
-//             12 => Self::Whitehall,
-//             13 => Self::NorthumberlandAvenue,
-//             14 => Self::FenchurchStreetStation,
-//             15 => Self::BowStreet,
-//             16 => Self::CommunityChest1,
-//             17 => Self::GreatMarlboroughStreet,
-//             18 => Self::VineStreet,
-//             19 => Self::FreeParking,
-//             20 => Self::TheStrand,
-//             21 => Self::Chance2,
-//             22 => Self::FleetStreet,
-//             23 => Self::TrafalgarSquare,
-//             24 => Self::KingCrossStation,
-//             25 => Self::LeicesterSquare,
-//             26 => Self::CoventryStreet,
-//             27 => Self::WaterCompany,
-//             28 => Self::Piccadilly,
-//             29 => Self::GoToJail,
-//             30 => Self::RegentStreet,
-//             31 => Self::OxfordStreet,
-//             32 => Self::CommunityChest2,
-//             33 => Self::BondStreet,
-//             34 => Self::LiverpoolStreetStation,
-//             35 => Self::Chance3,
-//             36 => Self::ParkLane,
-//             37 => Self::LuxuryTax,
-//             _ => Self::Mayfair,
-//         }
-//     }
-// }
-
-// #[cfg(test)]
-// mod test {
-//     use super::BoardLocationName;
-
-//     #[test]
-//     fn get_location() {
-//         let current_location = 3;
-//         let move_steps = 3;
-//         assert_eq!(
-//             BoardLocationName::position(current_location),
-//             BoardLocationName::IncomeTax
-//         );
-//         assert_eq!(
-//             BoardLocationName::position(current_location + move_steps),
-//             BoardLocationName::Chance1
-//         );
-//     }
-// }
+/// What happens when a player lands on a square
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SquareKind {
+    Go,
+    Street,
+    Station,
+    Utility,
+    Tax,
+    Chance,
+    CommunityChest,
+    Jail,
+    FreeParking,
+    GoToJail,
+}
+
+impl SquareKind {
+    pub const fn is_ownable(self) -> bool {
+        matches!(self, Self::Street | Self::Station | Self::Utility)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ColourGroup {
+    Brown,
+    LightBlue,
+    Pink,
+    Orange,
+    Red,
+    Yellow,
+    Green,
+    DarkBlue,
+    Station,
+    Utility,
+}
+
+impl ColourGroup {
+    pub const ALL: [Self; 10] = [
+        Self::Brown,
+        Self::LightBlue,
+        Self::Pink,
+        Self::Orange,
+        Self::Red,
+        Self::Yellow,
+        Self::Green,
+        Self::DarkBlue,
+        Self::Station,
+        Self::Utility,
+    ];
+
+    /// Position in `ColourGroup::ALL`
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Houses and hotels can only be built on the coloured streets
+    pub const fn is_buildable(self) -> bool {
+        !matches!(self, Self::Station | Self::Utility)
+    }
+}
+
+impl BoardLocation {
+    /// Every square in board order, starting from Go
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..).map_while(|position| Self::try_from(position).ok())
+    }
+
+    pub fn position(self) -> usize {
+        u16::from(self) as usize
+    }
+
+    pub fn from_position(position: usize) -> Option<Self> {
+        u16::try_from(position)
+            .ok()
+            .and_then(|p| Self::try_from(p).ok())
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Go => "Go",
+            Self::OldKentRoad => "Old Kent Road",
+            Self::CommunityChest1 | Self::CommunityChest2 | Self::CommunityChest3 => {
+                "Community Chest"
+            }
+            Self::WhitechapelRoad => "Whitechapel Road",
+            Self::IncomeTax => "Income Tax",
+            Self::KingCrossStation => "King's Cross Station",
+            Self::TheAngelIslington => "The Angel Islington",
+            Self::Chance1 | Self::Chance2 | Self::Chance3 => "Chance",
+            Self::EustonRoad => "Euston Road",
+            Self::PentonvilleRoad => "Pentonville Road",
+            Self::Jail => "Jail",
+            Self::PallMall => "Pall Mall",
+            Self::ElectricCompany => "Electric Company",
+            Self::Whitehall => "Whitehall",
+            Self::NorthumberlandAvenue => "Northumberland Avenue",
+            Self::MaryleboneStation => "Marylebone Station",
+            Self::BowStreet => "Bow Street",
+            Self::GreatMarlboroughStreet => "Great Marlborough Street",
+            Self::VineStreet => "Vine Street",
+            Self::FreeParking => "Free Parking",
+            Self::TheStrand => "The Strand",
+            Self::FleetStreet => "Fleet Street",
+            Self::TrafalgarSquare => "Trafalgar Square",
+            Self::FenchurchStreetStation => "Fenchurch Street Station",
+            Self::LeicesterSquare => "Leicester Square",
+            Self::CoventryStreet => "Coventry Street",
+            Self::WaterCompany => "Water Works",
+            Self::Piccadilly => "Piccadilly",
+            Self::GoToJail => "Go To Jail",
+            Self::RegentStreet => "Regent Street",
+            Self::OxfordStreet => "Oxford Street",
+            Self::BondStreet => "Bond Street",
+            Self::LiverpoolStreetStation => "Liverpool Street Station",
+            Self::ParkLane => "Park Lane",
+            Self::LuxuryTax => "Super Tax",
+            Self::Mayfair => "Mayfair",
+        }
+    }
+
+    pub const fn kind(self) -> SquareKind {
+        match self {
+            Self::Go => SquareKind::Go,
+            Self::IncomeTax | Self::LuxuryTax => SquareKind::Tax,
+            Self::Chance1 | Self::Chance2 | Self::Chance3 => SquareKind::Chance,
+            Self::CommunityChest1 | Self::CommunityChest2 | Self::CommunityChest3 => {
+                SquareKind::CommunityChest
+            }
+            Self::Jail => SquareKind::Jail,
+            Self::FreeParking => SquareKind::FreeParking,
+            Self::GoToJail => SquareKind::GoToJail,
+            _ => match self.colour_group() {
+                Some(ColourGroup::Station) => SquareKind::Station,
+                Some(ColourGroup::Utility) => SquareKind::Utility,
+                _ => SquareKind::Street,
+            },
+        }
+    }
+
+    pub const fn colour_group(self) -> Option<ColourGroup> {
+        match self {
+            Self::OldKentRoad | Self::WhitechapelRoad => Some(ColourGroup::Brown),
+            Self::TheAngelIslington | Self::EustonRoad | Self::PentonvilleRoad => {
+                Some(ColourGroup::LightBlue)
+            }
+            Self::PallMall | Self::Whitehall | Self::NorthumberlandAvenue => {
+                Some(ColourGroup::Pink)
+            }
+            Self::BowStreet | Self::GreatMarlboroughStreet | Self::VineStreet => {
+                Some(ColourGroup::Orange)
+            }
+            Self::TheStrand | Self::FleetStreet | Self::TrafalgarSquare => Some(ColourGroup::Red),
+            Self::LeicesterSquare | Self::CoventryStreet | Self::Piccadilly => {
+                Some(ColourGroup::Yellow)
+            }
+            Self::RegentStreet | Self::OxfordStreet | Self::BondStreet => Some(ColourGroup::Green),
+            Self::ParkLane | Self::Mayfair => Some(ColourGroup::DarkBlue),
+            Self::MaryleboneStation
+            | Self::FenchurchStreetStation
+            | Self::KingCrossStation
+            | Self::LiverpoolStreetStation => Some(ColourGroup::Station),
+            Self::ElectricCompany | Self::WaterCompany => Some(ColourGroup::Utility),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BoardLocation, SquareKind};
+
+    #[test]
+    fn get_location() {
+        let current_location = 4;
+        let move_steps = 3;
+        assert_eq!(
+            BoardLocation::from_position(current_location),
+            Some(BoardLocation::IncomeTax)
+        );
+        assert_eq!(
+            BoardLocation::from_position(current_location + move_steps),
+            Some(BoardLocation::Chance1)
+        );
+        assert_eq!(BoardLocation::Mayfair.position(), 39);
+        assert_eq!(BoardLocation::all().count(), 40);
+    }
+
+    #[test]
+    fn square_kinds() {
+        assert_eq!(BoardLocation::Jail.kind(), SquareKind::Jail);
+        assert_eq!(
+            BoardLocation::FenchurchStreetStation.kind(),
+            SquareKind::Station
+        );
+        assert_eq!(BoardLocation::Mayfair.kind(), SquareKind::Street);
+        assert!(!BoardLocation::LuxuryTax.kind().is_ownable());
+    }
+}
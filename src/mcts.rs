@@ -0,0 +1,284 @@
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{
+    action::{Action, PendingDecision},
+    agent::Agent,
+    game::Game,
+    strategy::HeuristicAgent,
+    view::GameView,
+};
+
+/// How long the agent may think about each decision
+#[derive(Debug, Clone, Copy)]
+pub enum Budget {
+    Iterations(usize),
+    Time(Duration),
+}
+
+impl Budget {
+    /// Reading the clock panics on wasm32-unknown-unknown, so it's only started for `Time`
+    fn start(self) -> Option<Instant> {
+        match self {
+            Self::Iterations(_) => None,
+            Self::Time(_) => Some(Instant::now()),
+        }
+    }
+
+    fn exhausted(self, started: Option<Instant>, iterations: usize) -> bool {
+        match (self, started) {
+            (Self::Iterations(limit), _) => iterations >= limit,
+            (Self::Time(limit), Some(started)) => started.elapsed() >= limit,
+            (Self::Time(_), None) => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MctsConfig {
+    pub budget: Budget,
+    /// UCB exploration constant
+    pub exploration: f64,
+    /// Rounds played out by the default policy before the position is scored
+    pub rollout_rounds: usize,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            budget: Budget::Iterations(200),
+            exploration: std::f64::consts::SQRT_2,
+            rollout_rounds: 20,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    action: Option<Action>,
+    /// Seat of the player who chose `action`
+    seat: usize,
+    children: Vec<usize>,
+    visits: usize,
+    /// Number of times the action was legal when its parent was visited
+    available: usize,
+    value: f64,
+}
+
+impl Node {
+    const fn new(action: Option<Action>, seat: usize) -> Self {
+        Self {
+            action,
+            seat,
+            children: vec![],
+            visits: 0,
+            available: 1,
+            value: 0.0,
+        }
+    }
+
+    fn ucb(&self, exploration: f64) -> f64 {
+        if self.visits == 0 {
+            return f64::INFINITY;
+        }
+        let visits = self.visits as f64;
+        self.value / visits + exploration * ((self.available as f64).ln() / visits).sqrt()
+    }
+}
+
+/// Information set Monte Carlo Tree Search. Each iteration works on a clone of the
+/// game with freshly seeded dice and cards, so chance is re-sampled on every pass
+/// through the tree and the hidden card order is never looked at.
+#[derive(Debug, Clone)]
+pub struct MctsAgent {
+    config: MctsConfig,
+    rng: StdRng,
+}
+
+impl MctsAgent {
+    pub fn new(config: MctsConfig, seed: u64) -> Self {
+        Self {
+            config,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn search(&mut self, view: &GameView<'_>) -> Option<Action> {
+        let mut tree = vec![Node::new(None, 0)];
+        let started = self.config.budget.start();
+        let mut iterations = 0;
+
+        while !self.config.budget.exhausted(started, iterations) {
+            let mut state = view.determinize(self.rng.gen());
+
+            let path = self.select_and_expand(&mut tree, &mut state);
+            let rewards = self.rollout(&mut state);
+            tree[0].visits += 1;
+            for &n in &path {
+                let node = &mut tree[n];
+                node.visits += 1;
+                node.value += rewards[node.seat];
+            }
+            iterations += 1;
+        }
+
+        let legal = view.legal_actions();
+        tree[0]
+            .children
+            .iter()
+            .map(|&c| &tree[c])
+            .filter(|node| node.action.as_ref().is_some_and(|a| legal.contains(a)))
+            .max_by_key(|node| node.visits)
+            .and_then(|node| node.action.clone())
+    }
+
+    /// Walk down the tree with UCB until an untried action is found, then add it
+    fn select_and_expand(&mut self, tree: &mut Vec<Node>, state: &mut Game) -> Vec<usize> {
+        let mut path = vec![];
+        let mut node = 0;
+
+        while let Some(pending) = state.pending() {
+            let Ok(seat) = state.seat(pending.player) else {
+                break;
+            };
+            let legal = state.legal_actions();
+            let mut untried = legal.clone();
+            for c in tree[node].children.clone() {
+                if let Some(action) = &tree[c].action {
+                    if legal.contains(action) {
+                        untried.retain(|a| a != action);
+                        tree[c].available += 1;
+                    }
+                }
+            }
+
+            if let Some(action) = untried.choose(&mut self.rng).cloned() {
+                tree.push(Node::new(Some(action.clone()), seat));
+                let child = tree.len() - 1;
+                tree[node].children.push(child);
+                path.push(child);
+                let _ = state.apply_or_default(action);
+                break;
+            }
+
+            let exploration = self.config.exploration;
+            let best = tree[node]
+                .children
+                .iter()
+                .copied()
+                .filter(|&c| tree[c].action.as_ref().is_some_and(|a| legal.contains(a)))
+                .max_by(|&a, &b| {
+                    tree[a]
+                        .ucb(exploration)
+                        .total_cmp(&tree[b].ucb(exploration))
+                });
+
+            match best {
+                Some(child) => {
+                    path.push(child);
+                    node = child;
+                    let applied = match tree[child].action.clone() {
+                        Some(action) => state.apply_or_default(action),
+                        None => Ok(()),
+                    };
+                    if applied.is_err() {
+                        break;
+                    }
+                }
+                None => break,
+            }
+        }
+
+        path
+    }
+
+    /// Play on with the cheap greedy policy, then score each seat: 1 for the winner,
+    /// or its share of the total net worth if the game is still going
+    fn rollout(&self, state: &mut Game) -> Vec<f64> {
+        let limit = state.rounds() + self.config.rollout_rounds;
+        let mut policy = HeuristicAgent::new(0);
+        while let Some(pending) = state.pending() {
+            if state.rounds() >= limit {
+                break;
+            }
+            let action = policy.decide(&state.view(), &pending);
+            if state.apply_or_default(action).is_err() {
+                break;
+            }
+        }
+
+        match state.winner() {
+            Some(winner) => state
+                .players
+                .iter()
+                .map(|p| (p.id == winner) as u8 as f64)
+                .collect(),
+            None => {
+                let worth: Vec<f64> = state
+                    .players
+                    .iter()
+                    .map(|p| state.holdings(p).net_worth() as f64)
+                    .collect();
+                let total: f64 = worth.iter().sum::<f64>().max(1.0);
+                worth.iter().map(|w| w / total).collect()
+            }
+        }
+    }
+}
+
+impl Agent for MctsAgent {
+    fn decide(&mut self, view: &GameView<'_>, _pending: &PendingDecision) -> Action {
+        let mut legal = view.legal_actions();
+        if legal.len() <= 1 {
+            return legal.pop().unwrap_or(Action::EndTurn);
+        }
+
+        self.search(view).unwrap_or_else(|| legal.swap_remove(0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use crate::{agent::Agent, game::Game};
+
+    use super::{Budget, MctsAgent, MctsConfig};
+
+    #[test]
+    fn picks_a_legal_action() {
+        let mut game = Game::new(3, 4);
+        let mut agent = MctsAgent::new(
+            MctsConfig {
+                budget: Budget::Iterations(30),
+                rollout_rounds: 5,
+                ..MctsConfig::default()
+            },
+            1,
+        );
+
+        for _ in 0..20 {
+            let pending = game.pending().unwrap();
+            let action = agent.decide(&game.view(), &pending);
+            assert!(game.is_legal(&action));
+            game.apply(action).unwrap();
+        }
+    }
+
+    #[test]
+    fn respects_the_time_budget() {
+        let game = Game::new(4, 8);
+        let mut agent = MctsAgent::new(
+            MctsConfig {
+                budget: Budget::Time(Duration::from_millis(50)),
+                ..MctsConfig::default()
+            },
+            2,
+        );
+
+        let started = Instant::now();
+        agent.search(&game.view());
+        assert!(started.elapsed() < Duration::from_millis(500));
+    }
+}
@@ -1,31 +1,106 @@
-use std::cell::RefCell;
+use std::{cell::RefCell, collections::VecDeque};
 
-use rand::prelude::ThreadRng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
+#[cfg(feature = "entropy")]
 use crate::config::Property;
+use crate::locations::SquareKind;
+use crate::rules::Rules;
+use crate::square::{CHANCE_CARDS, COMMUNITY_CHEST_CARDS};
 use crate::state::State;
 
-#[derive(Debug)]
-pub struct Count(usize);
+#[derive(Debug, Clone)]
+pub struct Count(pub usize);
 
-#[derive(Debug)]
+/// Money the bank has handed out and taken in, for checking that cash is conserved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bank {
+    /// Cash held by the players and the pot when the game started
+    pub opening: usize,
+    pub paid_out: usize,
+    pub received: usize,
+}
+
+impl Bank {
+    /// What the players and the pot should be holding between them
+    pub const fn expected_cash(&self) -> usize {
+        (self.opening + self.paid_out).saturating_sub(self.received)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct MonopolyState {
     pub free_parking: RefCell<Count>,
     cycles: RefCell<Count>,
-    pub rng: ThreadRng,
+    pub rng: StdRng,
+    pub bank: Bank,
+    pub rules: Rules,
+    /// Cards that will be drawn before any random ones, e.g. to set up a test
+    stacked_chance: VecDeque<usize>,
+    stacked_community_chest: VecDeque<usize>,
 }
 
 impl MonopolyState {
+    #[cfg(feature = "entropy")]
     pub fn new(_property_config: Vec<Property>) -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Reproducible games for experiments and training environments
+    pub fn seeded(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    pub const fn with_rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Re-draw all future dice rolls and cards, e.g. for each search iteration
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+        self.stacked_chance.clear();
+        self.stacked_community_chest.clear();
+    }
+
+    fn with_rng(rng: StdRng) -> Self {
         Self {
             free_parking: RefCell::new(Count(0)),
             cycles: RefCell::new(Count(0)),
-            rng: ThreadRng::default(),
+            rng,
+            bank: Bank::default(),
+            rules: Rules::default(),
+            stacked_chance: VecDeque::new(),
+            stacked_community_chest: VecDeque::new(),
+        }
+    }
+
+    /// Put `cards` on top of the Chance or Community Chest pile, first drawn first
+    pub fn stack_cards(&mut self, kind: SquareKind, cards: &[usize]) {
+        match kind {
+            SquareKind::Chance => self.stacked_chance.extend(cards),
+            SquareKind::CommunityChest => self.stacked_community_chest.extend(cards),
+            _ => {}
         }
     }
+
+    pub fn draw_card(&mut self, kind: SquareKind) -> usize {
+        let (stacked, cards) = match kind {
+            SquareKind::Chance => (&mut self.stacked_chance, CHANCE_CARDS),
+            _ => (&mut self.stacked_community_chest, COMMUNITY_CHEST_CARDS),
+        };
+        match stacked.pop_front() {
+            Some(card) => card,
+            None => self.rng.gen_range(1..=cards),
+        }
+    }
+
+    pub fn cycles(&self) -> usize {
+        self.cycles.borrow().0
+    }
 }
 
-impl<'a> State for MonopolyState {
+impl State for MonopolyState {
     fn update(&self) {
         self.cycles.borrow_mut().0 += 1;
         log::info!("Cycle [{}] complete", self.cycles.borrow().0);
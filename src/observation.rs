@@ -0,0 +1,299 @@
+use crate::{
+    game::Game,
+    player::{Player, PlayerId, PlayerState},
+    square::{BoardSquare, PropertyState},
+};
+
+/// Seats reserved in the encoding, unused seats are left as zeros
+pub const MAX_PLAYERS: usize = crate::roster::MAX_PLAYERS;
+
+pub const SQUARE_FEATURES: usize = 5;
+pub const SQUARE_OWNABLE: usize = 0;
+/// 0 for the bank, 1 for the observer, 2 for the next player in turn order, ...
+pub const SQUARE_OWNER: usize = 1;
+/// Up to 4, the hotel counts under `SQUARE_HOTELS` instead
+pub const SQUARE_HOUSES: usize = 2;
+pub const SQUARE_HOTELS: usize = 3;
+pub const SQUARE_MORTGAGED: usize = 4;
+
+pub const PLAYER_FEATURES: usize = 9;
+pub const PLAYER_PRESENT: usize = 0;
+pub const PLAYER_ACTIVE: usize = 1;
+pub const PLAYER_TURN: usize = 2;
+pub const PLAYER_POSITION: usize = 3;
+pub const PLAYER_BALANCE: usize = 4;
+/// 0 when free, otherwise one more than the turns already spent in jail
+pub const PLAYER_JAIL: usize = 5;
+pub const PLAYER_JAIL_CARD: usize = 6;
+pub const PLAYER_GO_COUNT: usize = 7;
+pub const PLAYER_JAIL_COUNT: usize = 8;
+
+pub const GLOBAL_FEATURES: usize = 1;
+pub const FREE_PARKING: usize = 0;
+
+/// The game as one player sees it. Players are listed starting from the
+/// observer and then in turn order, so rotating the seats changes nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub players: Vec<(PlayerId, PlayerState)>,
+    pub properties: Vec<PropertyState>,
+    pub turn: Option<PlayerId>,
+    pub free_parking: usize,
+}
+
+impl Snapshot {
+    pub fn from_game(game: &Game, observer: PlayerId) -> Self {
+        Self::from_parts(
+            &game.players,
+            &game.board,
+            game.current_player(),
+            game.state.free_parking.borrow().0,
+            observer,
+        )
+    }
+
+    pub fn from_parts(
+        players: &[Player],
+        board: &[BoardSquare],
+        turn: PlayerId,
+        free_parking: usize,
+        observer: PlayerId,
+    ) -> Self {
+        let start = players
+            .iter()
+            .position(|p| p.id == observer)
+            .unwrap_or_default();
+
+        Self {
+            players: (0..players.len())
+                .map(|k| &players[(start + k) % players.len()])
+                .map(|p| (p.id, p.state.borrow().clone()))
+                .collect(),
+            properties: board.iter().map(BoardSquare::property_state).collect(),
+            turn: Some(turn),
+            free_parking,
+        }
+    }
+
+    pub fn observer(&self) -> Option<PlayerId> {
+        self.players.first().map(|(id, _)| *id)
+    }
+}
+
+/// Fixed-length numeric encoding of a `Snapshot`. The features are laid out as
+///
+/// | block   | starts at                                    | length                            |
+/// |---------|----------------------------------------------|-----------------------------------|
+/// | squares | `0`                                          | `board_size * SQUARE_FEATURES`    |
+/// | players | `board_size * SQUARE_FEATURES`               | `MAX_PLAYERS * PLAYER_FEATURES`   |
+/// | global  | `.. + MAX_PLAYERS * PLAYER_FEATURES`         | `GLOBAL_FEATURES`                 |
+///
+/// with the `SQUARE_*`, `PLAYER_*` and `FREE_PARKING` constants giving the
+/// position within each block. Every value is a whole number stored exactly,
+/// so `decode` gives back the state that was encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct Encoder {
+    board_size: usize,
+}
+
+impl Encoder {
+    pub const fn new(board_size: usize) -> Self {
+        Self { board_size }
+    }
+
+    pub const fn size(&self) -> usize {
+        self.global_index(GLOBAL_FEATURES)
+    }
+
+    pub const fn square_index(&self, position: usize, feature: usize) -> usize {
+        position * SQUARE_FEATURES + feature
+    }
+
+    /// `seat` is relative to the observer, who is always seat 0
+    pub const fn player_index(&self, seat: usize, feature: usize) -> usize {
+        self.board_size * SQUARE_FEATURES + seat * PLAYER_FEATURES + feature
+    }
+
+    pub const fn global_index(&self, feature: usize) -> usize {
+        self.player_index(MAX_PLAYERS, feature)
+    }
+
+    pub fn encode_game(&self, game: &Game, observer: PlayerId) -> Vec<f32> {
+        self.encode(&Snapshot::from_game(game, observer))
+    }
+
+    pub fn encode(&self, snapshot: &Snapshot) -> Vec<f32> {
+        let mut features = vec![0.0; self.size()];
+        let flag = |b: bool| if b { 1.0 } else { 0.0 };
+        let seat_of = |id: PlayerId| {
+            snapshot
+                .players
+                .iter()
+                .position(|(p, _)| *p == id)
+                .map_or(0.0, |seat| (seat + 1) as f32)
+        };
+
+        for (position, property) in snapshot.properties.iter().enumerate() {
+            let mut set = |feature, value| features[self.square_index(position, feature)] = value;
+            set(SQUARE_OWNABLE, flag(property.ownable));
+            set(SQUARE_OWNER, property.owner.map_or(0.0, seat_of));
+            let (houses, hotels) = property.buildings();
+            set(SQUARE_HOUSES, houses as f32);
+            set(SQUARE_HOTELS, hotels as f32);
+            set(SQUARE_MORTGAGED, flag(property.mortgaged));
+        }
+
+        for (seat, (id, s)) in snapshot.players.iter().take(MAX_PLAYERS).enumerate() {
+            let mut set = |feature, value| features[self.player_index(seat, feature)] = value;
+            set(PLAYER_PRESENT, 1.0);
+            set(PLAYER_ACTIVE, flag(s.active));
+            set(PLAYER_TURN, flag(snapshot.turn == Some(*id)));
+            set(PLAYER_POSITION, s.current_position as f32);
+            set(PLAYER_BALANCE, s.balance as f32);
+            set(PLAYER_JAIL, s.jail.map_or(0.0, |days| (days + 1) as f32));
+            set(PLAYER_JAIL_CARD, flag(s.jail_card));
+            set(PLAYER_GO_COUNT, s.go_count as f32);
+            set(PLAYER_JAIL_COUNT, s.jail_count as f32);
+        }
+
+        features[self.global_index(FREE_PARKING)] = snapshot.free_parking as f32;
+        features
+    }
+
+    /// Rebuild the snapshot, `ids` being the player ids listed from the observer onwards
+    pub fn decode(&self, features: &[f32], ids: &[PlayerId]) -> Snapshot {
+        let whole = |index: usize| features[index].round() as usize;
+        let flag = |index: usize| features[index] > 0.5;
+
+        let mut turn = None;
+        let players = ids
+            .iter()
+            .take(MAX_PLAYERS)
+            .enumerate()
+            .filter(|(seat, _)| flag(self.player_index(*seat, PLAYER_PRESENT)))
+            .map(|(seat, id)| {
+                let at = |feature| self.player_index(seat, feature);
+                if flag(at(PLAYER_TURN)) {
+                    turn = Some(*id);
+                }
+                let state = PlayerState {
+                    current_position: whole(at(PLAYER_POSITION)),
+                    jail: whole(at(PLAYER_JAIL)).checked_sub(1),
+                    balance: whole(at(PLAYER_BALANCE)),
+                    active: flag(at(PLAYER_ACTIVE)),
+                    go_count: whole(at(PLAYER_GO_COUNT)),
+                    jail_count: whole(at(PLAYER_JAIL_COUNT)),
+                    jail_card: flag(at(PLAYER_JAIL_CARD)),
+                };
+                (*id, state)
+            })
+            .collect();
+
+        let properties = (0..self.board_size)
+            .map(|position| {
+                let at = |feature| self.square_index(position, feature);
+                PropertyState {
+                    ownable: flag(at(SQUARE_OWNABLE)),
+                    owner: whole(at(SQUARE_OWNER))
+                        .checked_sub(1)
+                        .and_then(|seat| ids.get(seat).copied()),
+                    // Back to the engine's fifth house
+                    house_count: whole(at(SQUARE_HOUSES)) + whole(at(SQUARE_HOTELS)),
+                    hotel_count: 0,
+                    mortgaged: flag(at(SQUARE_MORTGAGED)),
+                }
+            })
+            .collect();
+
+        Snapshot {
+            players,
+            properties,
+            turn,
+            free_parking: whole(self.global_index(FREE_PARKING)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{action::Decision, game::Game, locations::BoardLocation, scenario::Scenario};
+
+    use super::{Encoder, Snapshot, PLAYER_TURN, SQUARE_HOTELS, SQUARE_HOUSES, SQUARE_OWNER};
+
+    fn mid_game() -> Game {
+        let mut game = Game::new(4, 21);
+        while game.rounds() < 5 && !game.is_over() {
+            let action = match game.pending().unwrap().decision {
+                Decision::Buy { .. } => game.legal_actions().pop().unwrap(),
+                _ => game.legal_actions().swap_remove(0),
+            };
+            game.apply(action).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn encoding_round_trips() {
+        let game = mid_game();
+        let encoder = Encoder::new(game.board.len());
+        let snapshot = Snapshot::from_game(&game, 3);
+        let ids: Vec<_> = snapshot.players.iter().map(|(id, _)| *id).collect();
+
+        let features = encoder.encode(&snapshot);
+        assert_eq!(features.len(), encoder.size());
+        assert_eq!(encoder.decode(&features, &ids), snapshot);
+    }
+
+    #[test]
+    fn hotels_are_encoded_apart_from_houses() {
+        let mayfair = BoardLocation::Mayfair.position();
+        let park_lane = BoardLocation::ParkLane.position();
+        let game = Scenario::new(2)
+            .owner(mayfair, 1)
+            .owner(park_lane, 1)
+            .houses(mayfair, 5)
+            .houses(park_lane, 4)
+            .build()
+            .unwrap();
+        let encoder = Encoder::new(game.board.len());
+        let snapshot = Snapshot::from_game(&game, 1);
+        let features = encoder.encode(&snapshot);
+
+        let at = |position, feature| features[encoder.square_index(position, feature)];
+        assert_eq!(
+            (at(mayfair, SQUARE_HOUSES), at(mayfair, SQUARE_HOTELS)),
+            (4.0, 1.0)
+        );
+        assert_eq!(
+            (at(park_lane, SQUARE_HOUSES), at(park_lane, SQUARE_HOTELS)),
+            (4.0, 0.0)
+        );
+        assert_eq!(encoder.decode(&features, &[1, 2]), snapshot);
+    }
+
+    #[test]
+    fn encoding_ignores_seat_rotation() {
+        let game = mid_game();
+        let encoder = Encoder::new(game.board.len());
+        let turn = game.current_player();
+        let encode =
+            |players: &[_]| encoder.encode(&Snapshot::from_parts(players, &game.board, turn, 0, 2));
+
+        let mut rotated = game.players.clone();
+        rotated.rotate_left(1);
+        assert_eq!(encode(&game.players), encode(&rotated));
+
+        let features = encode(&game.players);
+        let owned_by_observer = game
+            .board
+            .iter()
+            .position(|sq| sq.owner_id() == Some(2))
+            .expect("the observer owns a square mid game");
+        assert_eq!(
+            features[encoder.square_index(owned_by_observer, SQUARE_OWNER)],
+            1.0
+        );
+        let turn_seat = (game.seat(turn).unwrap() + 4 - game.seat(2).unwrap()) % 4;
+        assert_eq!(features[encoder.player_index(turn_seat, PLAYER_TURN)], 1.0);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::player::PlayerId;
+#[derive(Debug)]
 pub struct Payment {
     pub to: PlayerId,
     pub amount: usize,
@@ -0,0 +1,147 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Where the current turn has got to. The engine only ever moves along one of
+/// `TRANSITIONS`, so the turn diagram can be drawn straight from the code.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Phase {
+    StartTurn,
+    /// Build, mortgage and trade before rolling
+    PreRoll,
+    /// Pay bail, use a card or roll for doubles
+    Jail,
+    Roll,
+    Move,
+    /// Rent, tax, cards and buying or auctioning the square landed on
+    Resolve,
+    DoublesReroll,
+    /// Build, mortgage and trade once the dice are done with
+    PostRoll,
+    EndTurn,
+    GameOver,
+}
+
+impl Phase {
+    pub const ALL: [Self; 10] = [
+        Self::StartTurn,
+        Self::PreRoll,
+        Self::Jail,
+        Self::Roll,
+        Self::Move,
+        Self::Resolve,
+        Self::DoublesReroll,
+        Self::PostRoll,
+        Self::EndTurn,
+        Self::GameOver,
+    ];
+
+    pub fn can_move_to(self, to: Self) -> bool {
+        TRANSITIONS.iter().any(|t| t.from == self && t.to == to)
+    }
+
+    pub fn transition(self, to: Self) -> Result<Self, InvalidTransition> {
+        if self.can_move_to(to) {
+            Ok(to)
+        } else {
+            Err(InvalidTransition { from: self, to })
+        }
+    }
+}
+
+/// One edge of the turn diagram
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Transition {
+    pub from: Phase,
+    pub to: Phase,
+    /// Why the turn moves on, shown on the diagram
+    pub label: &'static str,
+}
+
+const fn edge(from: Phase, to: Phase, label: &'static str) -> Transition {
+    Transition { from, to, label }
+}
+
+pub const TRANSITIONS: [Transition; 19] = [
+    edge(Phase::StartTurn, Phase::PreRoll, "turn starts"),
+    edge(Phase::StartTurn, Phase::GameOver, "one player left"),
+    edge(Phase::PreRoll, Phase::Roll, "roll"),
+    edge(Phase::PreRoll, Phase::Jail, "roll while in jail"),
+    edge(Phase::Jail, Phase::Roll, "bail or card"),
+    edge(Phase::Jail, Phase::Move, "doubles or third attempt"),
+    edge(Phase::Jail, Phase::PostRoll, "stays in jail"),
+    edge(Phase::Jail, Phase::EndTurn, "bankrupt"),
+    edge(Phase::Roll, Phase::Move, "dice rolled"),
+    edge(Phase::Roll, Phase::PostRoll, "third doubles, jailed"),
+    edge(Phase::Move, Phase::Resolve, "lands"),
+    edge(Phase::Resolve, Phase::Move, "card or Mr. Monopoly"),
+    edge(Phase::Resolve, Phase::DoublesReroll, "doubles"),
+    edge(Phase::Resolve, Phase::PostRoll, "done"),
+    edge(Phase::Resolve, Phase::EndTurn, "bankrupt"),
+    edge(Phase::DoublesReroll, Phase::Roll, "roll again"),
+    edge(Phase::PostRoll, Phase::EndTurn, "end turn"),
+    edge(Phase::EndTurn, Phase::StartTurn, "next player"),
+    edge(Phase::EndTurn, Phase::GameOver, "end condition met"),
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InvalidTransition {
+    pub from: Phase,
+    pub to: Phase,
+}
+
+impl fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No transition from {:?} to {:?}", self.from, self.to)
+    }
+}
+
+impl std::error::Error for InvalidTransition {}
+
+/// The turn as a Graphviz digraph, e.g. `monopoly_abm --dot | dot -Tpdf`
+pub fn to_dot() -> String {
+    let mut dot = String::from("digraph turn {\n    rankdir=TB;\n    node [shape=box];\n");
+    for phase in &Phase::ALL {
+        dot.push_str(&format!("    {:?};\n", phase));
+    }
+    for t in &TRANSITIONS {
+        dot.push_str(&format!(
+            "    {:?} -> {:?} [label=\"{}\"];\n",
+            t.from, t.to, t.label
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod test {
+    use super::{to_dot, InvalidTransition, Phase, TRANSITIONS};
+
+    #[test]
+    fn transitions_are_validated() {
+        assert_eq!(Phase::PreRoll.transition(Phase::Roll), Ok(Phase::Roll));
+        assert_eq!(
+            Phase::PostRoll.transition(Phase::Roll),
+            Err(InvalidTransition {
+                from: Phase::PostRoll,
+                to: Phase::Roll
+            })
+        );
+
+        // Every phase can be reached, and every phase but the last can be left
+        for phase in &Phase::ALL {
+            assert!(TRANSITIONS.iter().any(|t| t.to == *phase));
+            let leaves = TRANSITIONS.iter().any(|t| t.from == *phase);
+            assert_eq!(leaves, *phase != Phase::GameOver);
+        }
+    }
+
+    #[test]
+    fn exports_graphviz() {
+        let dot = to_dot();
+        assert!(dot.starts_with("digraph turn {"));
+        assert!(dot.contains("PreRoll -> Roll [label=\"roll\"];"));
+        assert_eq!(dot.matches("->").count(), TRANSITIONS.len());
+    }
+}
@@ -1,13 +1,14 @@
 use std::cell::RefCell;
 
-use crate::{agent::Agent, dice::roll_game_dice, monopoly::MonopolyState, token::Token};
+use crate::{error::MonopolyError, token::Token};
 
 pub type PlayerId = usize;
 
-const JAIL_BAIL: usize = 50;
+pub const JAIL_BAIL: usize = 50;
+pub const STARTING_CASH: usize = 1500;
 
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PlayerState {
     pub current_position: usize,
     pub jail: Option<usize>,
@@ -15,34 +16,42 @@ pub struct PlayerState {
     pub active: bool,
     pub go_count: usize,
     pub jail_count: usize,
-    pub house_count: usize,
-    pub hotel_count: usize,
     pub jail_card: bool,
 }
 
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Player {
     pub id: PlayerId,
+    pub name: String,
     pub token: Token,
     pub state: RefCell<PlayerState>,
 }
 
 impl Player {
     #[must_use]
-    pub const fn new(id: usize) -> Self {
+    pub fn new(id: usize) -> Self {
+        Self::with_details(
+            id,
+            &format!("Player {}", id),
+            Token::value_to_enum(id),
+            STARTING_CASH,
+        )
+    }
+
+    #[must_use]
+    pub fn with_details(id: usize, name: &str, token: Token, balance: usize) -> Self {
         Self {
             id,
-            token: Token::value_to_enum(id),
+            name: name.to_string(),
+            token,
             state: RefCell::new(PlayerState {
                 current_position: 0,
                 jail: None,
-                balance: 1500,
+                balance,
                 active: true,
                 go_count: 0,
                 jail_count: 0,
-                house_count: 0,
-                hotel_count: 0,
                 jail_card: false,
             }),
         }
@@ -59,29 +68,30 @@ impl Player {
         s.current_position
     }
 
-    pub fn jail_card(&self) {
+    /// Move forward around a board of `board_size` squares, returns true when Go was passed
+    pub fn advance(&self, steps: usize, board_size: usize) -> bool {
         let mut s = self.state.borrow_mut();
-        s.jail_card = true;
-    }
-
-    pub fn has_jail_card(&self) -> bool {
-        let s = self.state.borrow();
-        s.jail_card
+        let moved_to = s.current_position + steps;
+        s.current_position = moved_to % board_size;
+        if moved_to >= board_size {
+            s.go_count += 1;
+        }
+        moved_to > board_size
     }
 
-    pub fn add_house(&self) {
+    pub fn set_position(&self, position: usize) {
         let mut s = self.state.borrow_mut();
-        s.house_count += 1;
+        s.current_position = position;
     }
 
-    pub fn add_hotel(&self) {
+    pub fn jail_card(&self) {
         let mut s = self.state.borrow_mut();
-        s.hotel_count += 1;
+        s.jail_card = true;
     }
 
-    pub fn count_properties(&self) -> (usize, usize) {
+    pub fn has_jail_card(&self) -> bool {
         let s = self.state.borrow();
-        (s.house_count, s.hotel_count)
+        s.jail_card
     }
 
     pub fn is_active(&self) -> bool {
@@ -93,7 +103,7 @@ impl Player {
     }
 
     pub fn can_afford(&self, charge: usize) -> bool {
-        self.state.borrow().balance - charge > 0
+        self.state.borrow().balance >= charge
     }
 
     pub fn deposit(&self, value: usize) {
@@ -101,15 +111,29 @@ impl Player {
         s.balance += value;
     }
 
-    pub fn pay(&self, charge: usize) {
+    /// Leaves the balance alone when the charge can't be met, going bankrupt is up to the engine
+    pub fn pay(&self, charge: usize) -> Result<(), MonopolyError> {
         let mut s = self.state.borrow_mut();
-        if s.balance as i32 - charge as i32 > 0 {
-            s.balance -= charge;
-        } else {
-            s.active = false;
+        match s.balance.checked_sub(charge) {
+            Some(left) => {
+                s.balance = left;
+                Ok(())
+            }
+            _ => Err(MonopolyError::InsufficientFunds {
+                player: self.id,
+                amount: charge,
+                balance: s.balance,
+            }),
         }
     }
 
+    /// Leave the game, handing back whatever cash was left
+    pub fn declare_bankrupt(&self) -> usize {
+        let mut s = self.state.borrow_mut();
+        s.active = false;
+        std::mem::take(&mut s.balance)
+    }
+
     #[allow(dead_code)]
     pub fn go_to_jail(&self) -> usize {
         let mut s = self.state.borrow_mut();
@@ -137,6 +161,11 @@ impl Player {
     pub fn get_out_of_jail(&self) {
         let mut s = self.state.borrow_mut();
         s.jail = None;
+    }
+
+    pub fn use_jail_card(&self) {
+        let mut s = self.state.borrow_mut();
+        s.jail = None;
         s.jail_card = false;
     }
 
@@ -144,10 +173,14 @@ impl Player {
     pub fn update_jail(&self, is_double: bool, bail: usize) {
         let count = self.go_to_jail();
 
-        if is_double || self.has_jail_card() {
+        if is_double {
             self.get_out_of_jail();
+        } else if self.has_jail_card() {
+            self.use_jail_card();
         } else if count == 3 {
-            self.pay(bail);
+            if self.pay(bail).is_err() {
+                self.state.borrow_mut().active = false;
+            }
             self.get_out_of_jail();
         }
     }
@@ -163,39 +196,6 @@ impl Player {
     }
 }
 
-impl Agent for Player {
-    type SimState = MonopolyState;
-
-    fn step(&self, _state: &Self::SimState, _players: Vec<&Player>) {
-        let mut r = rand::thread_rng();
-        let mut roll_count = 0;
-
-        if self.in_jail() && self.is_active() {
-            let roll_result = roll_game_dice(&mut r);
-            self.update_jail(roll_result.is_double, JAIL_BAIL);
-            roll_count += 1;
-        }
-
-        if !self.in_jail() && self.is_active() {
-            loop {
-                let roll_result = roll_game_dice(&mut r);
-                if roll_result.is_double {
-                    roll_count += 1;
-                    if roll_count == 3 {
-                        self.go_to_jail();
-                        break;
-                    }
-                }
-                let _current_position = self.move_position(roll_result.value);
-
-                if !roll_result.is_double {
-                    break;
-                }
-            }
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::{Player, Token};
@@ -224,12 +224,18 @@ mod test {
         assert!(gamer_two.is_active());
         assert!(gamer_two.can_afford(100));
 
-        gamer_two.pay(1000);
+        gamer_two.pay(1000).unwrap();
         assert!(gamer_two.is_active());
         assert_eq!(gamer_two.current_balance(), 500);
 
-        gamer_two.pay(1000);
-        assert!(!gamer_two.is_active());
+        assert!(gamer_two.pay(1000).is_err());
+        assert_eq!(gamer_two.current_balance(), 500);
+
+        // Every last pound can be spent
+        assert!(gamer_two.can_afford(500));
+        gamer_two.pay(500).unwrap();
+        assert!(gamer_two.is_active());
+        assert_eq!(gamer_two.current_balance(), 0);
     }
 
     #[test]
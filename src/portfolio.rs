@@ -0,0 +1,198 @@
+use serde::Serialize;
+
+use crate::{
+    locations::{ColourGroup, SquareKind},
+    player::PlayerId,
+    square::{BoardPosition, BoardSquare},
+};
+
+/// One square a player owns
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Holding {
+    pub position: BoardPosition,
+    pub name: String,
+    pub kind: SquareKind,
+    pub group: Option<ColourGroup>,
+    pub price: usize,
+    pub houses: usize,
+    pub hotels: usize,
+    /// What the buildings on the square cost to put up
+    pub building_cost: usize,
+    pub mortgaged: bool,
+}
+
+impl Holding {
+    fn new(sq: &BoardSquare) -> Self {
+        let (houses, hotels) = sq.buildings();
+        Self {
+            position: sq.position(),
+            name: sq.name().to_string(),
+            kind: sq.kind(),
+            group: sq.colour_group(),
+            price: sq.get_purchase_cost(),
+            houses,
+            hotels,
+            building_cost: sq.building_value(),
+            mortgaged: sq.is_mortgaged(),
+        }
+    }
+}
+
+/// What a player owns, read off the board so it can never disagree with it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Portfolio {
+    pub player: PlayerId,
+    pub cash: usize,
+    pub holdings: Vec<Holding>,
+    /// Colour groups with every square owned by the player
+    pub complete_sets: Vec<ColourGroup>,
+    /// Price of each square (mortgage value if mortgaged) plus what its buildings cost
+    pub property_value: usize,
+    /// What selling every building and mortgaging every square would raise
+    pub liquidation_value: usize,
+}
+
+impl Portfolio {
+    pub fn new(player: PlayerId, cash: usize, board: &[BoardSquare]) -> Self {
+        let owned: Vec<&BoardSquare> = board
+            .iter()
+            .filter(|sq| sq.owner_id() == Some(player))
+            .collect();
+
+        let complete_sets = ColourGroup::ALL
+            .iter()
+            .copied()
+            .filter(|&group| {
+                let mut squares = board.iter().filter(|sq| sq.colour_group() == Some(group));
+                squares.clone().next().is_some() && squares.all(|sq| sq.owner_id() == Some(player))
+            })
+            .collect();
+
+        let property_value = owned
+            .iter()
+            .map(|sq| {
+                let value = if sq.is_mortgaged() {
+                    sq.mortgage_value()
+                } else {
+                    sq.get_purchase_cost()
+                };
+                value + sq.building_value()
+            })
+            .sum();
+        let liquidation_value = owned
+            .iter()
+            .filter(|sq| !sq.is_mortgaged())
+            .map(|sq| sq.building_sale_value() + sq.mortgage_value())
+            .sum();
+
+        Self {
+            player,
+            cash,
+            holdings: owned.into_iter().map(Holding::new).collect(),
+            complete_sets,
+            property_value,
+            liquidation_value,
+        }
+    }
+
+    /// Holdings of each colour group, in board order. Squares without a group are left out.
+    pub fn by_colour(&self) -> Vec<(ColourGroup, Vec<&Holding>)> {
+        ColourGroup::ALL
+            .iter()
+            .map(|&group| {
+                let held = self
+                    .holdings
+                    .iter()
+                    .filter(|h| h.group == Some(group))
+                    .collect();
+                (group, held)
+            })
+            .filter(|(_, held): &(_, Vec<_>)| !held.is_empty())
+            .collect()
+    }
+
+    /// Houses and hotels across every holding
+    pub fn buildings(&self) -> (usize, usize) {
+        self.holdings.iter().fold((0, 0), |(houses, hotels), h| {
+            (houses + h.houses, hotels + h.hotels)
+        })
+    }
+
+    pub fn mortgaged(&self) -> impl Iterator<Item = &Holding> {
+        self.holdings.iter().filter(|h| h.mortgaged)
+    }
+
+    pub fn owns_set(&self, group: ColourGroup) -> bool {
+        self.complete_sets.contains(&group)
+    }
+
+    /// Cash, the printed price of every square mortgaged or not, and what the
+    /// buildings cost. This is what Income Tax is charged on.
+    pub fn assessed_value(&self) -> usize {
+        self.cash
+            + self
+                .holdings
+                .iter()
+                .map(|h| h.price + h.building_cost)
+                .sum::<usize>()
+    }
+
+    pub const fn net_worth(&self) -> usize {
+        self.cash + self.property_value
+    }
+
+    /// Cash the player could lay their hands on without selling to another player
+    pub const fn liquid_assets(&self) -> usize {
+        self.cash + self.liquidation_value
+    }
+}
+
+/// A player's place on the leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Standing {
+    /// 1 for the leader
+    pub rank: usize,
+    pub player: PlayerId,
+    pub name: String,
+    pub active: bool,
+    pub cash: usize,
+    pub net_worth: usize,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{locations::ColourGroup, scenario::Scenario};
+
+    #[test]
+    fn portfolio_is_read_off_the_board() {
+        let game = Scenario::new(3)
+            .cash(1, 1000)
+            .owner(1, 1)
+            .owner(3, 1)
+            .houses(3, 5)
+            .owner(5, 1)
+            .mortgaged(5)
+            .owner(6, 2)
+            .build()
+            .unwrap();
+
+        let portfolio = game.portfolio(1).unwrap();
+        let positions: Vec<_> = portfolio.holdings.iter().map(|h| h.position).collect();
+        assert_eq!(positions, [1, 3, 5]);
+        assert_eq!(portfolio.complete_sets, [ColourGroup::Brown]);
+        assert!(portfolio.owns_set(ColourGroup::Brown));
+        assert_eq!(portfolio.buildings(), (4, 1));
+        assert_eq!(portfolio.by_colour().len(), 2);
+        assert_eq!(portfolio.mortgaged().count(), 1);
+
+        // 60 + 60 + 4 * 50 + 50 for the hotel, then half of the station's 200
+        assert_eq!(portfolio.property_value, 470);
+        assert_eq!(portfolio.net_worth(), 1470);
+        // Buildings sell at half, the station is already mortgaged
+        assert_eq!(portfolio.liquidation_value, 30 + 30 + 125);
+        assert_eq!(game.net_worth(1).unwrap(), portfolio.net_worth());
+
+        let leaders: Vec<_> = game.leaderboard().iter().map(|s| s.player).collect();
+        assert_eq!(leaders, [2, 3, 1]);
+    }
+}
@@ -0,0 +1,469 @@
+use std::fmt::Display;
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::json;
+
+use crate::{
+    action::{Action, PendingDecision},
+    agent::Agent,
+    batch::{run_batch, BatchConfig},
+    config::BoardConfig,
+    external::{Reply, Request},
+    game::Game,
+    gym::{EnvConfig, MonopolyEnv, Observation},
+    observation::Encoder,
+    player::PlayerId,
+    roster::{PlayerConfig, Roster},
+    strategy::Strategy,
+    termination::EndConditions,
+    view::GameView,
+};
+
+fn value_error(e: impl Display) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// By way of JSON, so Python gets plain dicts and lists
+fn to_py<T: Serialize + ?Sized>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(value_error)?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// Strategies are given by name, e.g. `"Greedy"`, or as a dict for `{"Tuned": {...}}`
+fn from_py<T: DeserializeOwned>(obj: &Bound<'_, PyAny>) -> PyResult<T> {
+    let json: String = obj
+        .py()
+        .import("json")?
+        .call_method1("dumps", (obj,))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(value_error)
+}
+
+/// A Python object with a `decide(request)` method. It is sent the same request as an
+/// external bot and answers the same way, with an index into `legal_actions` or an action.
+struct PyAgent {
+    agent: PyObject,
+    fallback: Box<dyn Agent>,
+}
+
+impl Agent for PyAgent {
+    fn decide(&mut self, view: &GameView<'_>, pending: &PendingDecision) -> Action {
+        let request = Request::new(view, pending);
+        let chosen = Python::with_gil(|py| {
+            let reply = self
+                .agent
+                .call_method1(py, "decide", (to_py(py, &request)?,))
+                .inspect_err(|e| e.display(py))?;
+            Ok::<_, PyErr>(from_py::<Reply>(reply.bind(py))?.action(&request.legal_actions))
+        });
+        match chosen {
+            Ok(Some(action)) if view.is_legal(&action) => action,
+            Ok(action) => {
+                log::warn!(
+                    "Python agent picked {:?}, falling back [Player={}]",
+                    action,
+                    pending.player
+                );
+                self.fallback.decide(view, pending)
+            }
+            Err(e) => {
+                log::warn!("{}, falling back [Player={}]", e, pending.player);
+                self.fallback.decide(view, pending)
+            }
+        }
+    }
+}
+
+#[pyclass(name = "Board")]
+#[derive(Clone)]
+pub struct PyBoard {
+    board: BoardConfig,
+}
+
+#[pymethods]
+impl PyBoard {
+    #[staticmethod]
+    fn uk() -> Self {
+        Self {
+            board: BoardConfig::uk(),
+        }
+    }
+
+    #[staticmethod]
+    fn us() -> Self {
+        Self {
+            board: BoardConfig::us(),
+        }
+    }
+
+    /// A preset by name or a board JSON file
+    #[staticmethod]
+    fn load(name_or_path: &str) -> PyResult<Self> {
+        Ok(Self {
+            board: BoardConfig::load(name_or_path).map_err(value_error)?,
+        })
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.board.name.clone()
+    }
+
+    fn __len__(&self) -> usize {
+        self.board.len()
+    }
+
+    fn squares(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.board.squares)
+    }
+}
+
+/// One game, played out in one go or a turn at a time, or decided by hand through
+/// `pending` and `apply`
+#[pyclass(name = "Game", unsendable)]
+pub struct PyGame {
+    game: Game,
+    agents: Vec<Box<dyn Agent>>,
+    max_rounds: usize,
+}
+
+#[pymethods]
+impl PyGame {
+    /// `players` are strategy names, or objects with a `decide(request)` method and
+    /// optionally a `name`, in turn order
+    #[new]
+    #[pyo3(signature = (players, board=None, seed=0, max_rounds=1000))]
+    fn new(
+        players: Vec<Bound<'_, PyAny>>,
+        board: Option<PyRef<'_, PyBoard>>,
+        seed: u64,
+        max_rounds: usize,
+    ) -> PyResult<Self> {
+        let mut configs = vec![];
+        let mut python_agents = vec![];
+        for (seat, player) in players.iter().enumerate() {
+            let default_name = format!("Player {}", seat + 1);
+            if player.hasattr("decide")? {
+                let name = match player.getattr("name") {
+                    Ok(name) => name.extract()?,
+                    Err(_) => default_name,
+                };
+                configs.push(PlayerConfig::new(&name, Strategy::Greedy));
+                python_agents.push((seat, player.clone().unbind()));
+            } else {
+                configs.push(PlayerConfig::new(&default_name, from_py(player)?));
+            }
+        }
+
+        let board = board.map_or_else(BoardConfig::uk, |b| b.board.clone());
+        let (mut game, mut agents) = Roster::new(configs)
+            .game(&board, seed)
+            .map_err(value_error)?;
+        for (seat, agent) in python_agents {
+            agents[seat] = Box::new(PyAgent {
+                agent,
+                fallback: Strategy::Greedy.agent(seed),
+            });
+        }
+        game.state.rules.end = EndConditions::last_standing().with_max_rounds(max_rounds);
+        game.record_events(true);
+
+        Ok(Self {
+            game,
+            agents,
+            max_rounds,
+        })
+    }
+
+    /// Play to the end and return the winner
+    fn play(&mut self) -> Option<PlayerId> {
+        self.game.play(&mut self.agents, self.max_rounds);
+        self.game.winner()
+    }
+
+    /// Play one turn, false once the game is over
+    fn step(&mut self) -> bool {
+        self.game.rounds() < self.max_rounds && self.game.play_turn(&mut self.agents)
+    }
+
+    #[getter]
+    fn rounds(&self) -> usize {
+        self.game.rounds()
+    }
+
+    #[getter]
+    fn is_over(&self) -> bool {
+        self.game.is_over()
+    }
+
+    #[getter]
+    fn winner(&self) -> Option<PlayerId> {
+        self.game.winner()
+    }
+
+    #[getter]
+    fn end_reason(&self) -> Option<String> {
+        self.game.end_reason().map(|reason| format!("{:?}", reason))
+    }
+
+    /// Players, squares and standings as everyone at the table sees them
+    fn state(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.game.view().table_state())
+    }
+
+    /// The decision the engine is waiting on, as a `decide` request
+    fn pending(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        match self.game.pending() {
+            Some(pending) => Ok(Some(to_py(py, &Request::new(&self.game.view(), &pending))?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Answer the pending decision by index or with an action
+    fn apply(&mut self, action: Bound<'_, PyAny>) -> PyResult<()> {
+        let legal_actions = self.game.legal_actions();
+        let action = from_py::<Reply>(&action)?
+            .action(&legal_actions)
+            .ok_or_else(|| value_error("No such action"))?;
+        self.game.apply(action).map_err(value_error)
+    }
+
+    fn leaderboard(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, &self.game.leaderboard())
+    }
+
+    fn events(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_py(py, self.game.events())
+    }
+}
+
+/// `run_batch` between the given strategies. Tables come back as dicts of equal length
+/// columns, ready for `pandas.DataFrame` or `pyarrow.table`.
+#[pyfunction]
+#[pyo3(signature = (strategies, board=None, games=100, max_rounds=500, seed=0))]
+fn batch(
+    py: Python<'_>,
+    strategies: Vec<Bound<'_, PyAny>>,
+    board: Option<PyRef<'_, PyBoard>>,
+    games: usize,
+    max_rounds: usize,
+    seed: u64,
+) -> PyResult<PyObject> {
+    let strategies: Vec<Strategy> = strategies.iter().map(from_py).collect::<PyResult<_>>()?;
+    let config = BatchConfig {
+        games,
+        max_rounds,
+        seed,
+    };
+    let board = board.map_or_else(BoardConfig::uk, |b| b.board.clone());
+    let result = py.allow_threads(|| {
+        run_batch(&board, strategies.len(), config, |e, seed| {
+            strategies[e].agent(seed)
+        })
+    });
+
+    let entrants = 0..strategies.len();
+    let squares = &board.squares;
+    let stats = &result.board.squares;
+    let tables = json!({
+        "games": result.games,
+        "unfinished": result.unfinished,
+        "mean_rounds": result.mean_rounds,
+        "entrants": {
+            "entrant": entrants.clone().collect::<Vec<_>>(),
+            "strategy": strategies.iter().map(|s| format!("{:?}", s)).collect::<Vec<_>>(),
+            "wins": result.wins,
+            "score": result.score,
+            "mean_score": entrants.map(|e| result.mean_score(e)).collect::<Vec<_>>(),
+        },
+        "squares": {
+            "position": (0..stats.len()).collect::<Vec<_>>(),
+            "name": squares.iter().map(|sq| sq.name.as_str()).collect::<Vec<_>>(),
+            "landings": stats.iter().map(|s| s.landings).collect::<Vec<_>>(),
+            "rent": stats.iter().map(|s| s.rent).collect::<Vec<_>>(),
+            "invested": stats.iter().map(|s| s.invested).collect::<Vec<_>>(),
+            "roi": stats.iter().map(|s| s.roi()).collect::<Vec<_>>(),
+        },
+    });
+    to_py(py, &tables)
+}
+
+/// `MonopolyEnv` with observations as `(features, action_mask)`
+#[pyclass(name = "Env", unsendable)]
+pub struct PyEnv {
+    env: MonopolyEnv,
+}
+
+fn observation(obs: Observation) -> (Vec<f32>, Vec<bool>) {
+    (obs.features, obs.action_mask)
+}
+
+#[pymethods]
+impl PyEnv {
+    /// `reward` is one of `WinLoss`, `NetWorthDelta` or `Survival`
+    #[new]
+    #[pyo3(signature = (opponents=None, learner_seat=0, reward="WinLoss", max_rounds=200))]
+    fn new(
+        opponents: Option<Vec<Bound<'_, PyAny>>>,
+        learner_seat: usize,
+        reward: &str,
+        max_rounds: usize,
+    ) -> PyResult<Self> {
+        let mut config = EnvConfig {
+            learner_seat,
+            max_rounds,
+            reward: serde_json::from_value(json!(reward)).map_err(value_error)?,
+            ..EnvConfig::default()
+        };
+        if let Some(opponents) = opponents {
+            config.opponents = opponents.iter().map(from_py).collect::<PyResult<_>>()?;
+        }
+        Ok(Self {
+            env: MonopolyEnv::new(config).map_err(value_error)?,
+        })
+    }
+
+    #[pyo3(signature = (seed=0))]
+    fn reset(&mut self, seed: u64) -> (Vec<f32>, Vec<bool>) {
+        observation(self.env.reset(seed))
+    }
+
+    /// `(observation, reward, done, info)`
+    #[allow(clippy::type_complexity)]
+    fn step(
+        &mut self,
+        py: Python<'_>,
+        action: usize,
+    ) -> PyResult<((Vec<f32>, Vec<bool>), f64, bool, PyObject)> {
+        let (obs, reward, done, info) = self.env.step(action);
+        let dict = PyDict::new(py);
+        dict.set_item("illegal_action", info.illegal_action)?;
+        dict.set_item("truncated", info.truncated)?;
+        dict.set_item("rounds", info.rounds)?;
+        dict.set_item("winner", info.winner)?;
+        Ok((observation(obs), reward, done, dict.into_any().unbind()))
+    }
+
+    #[getter]
+    fn action_space_size(&self) -> usize {
+        self.env.action_space().size()
+    }
+
+    #[getter]
+    fn observation_size(&self) -> usize {
+        Encoder::new(self.env.game().board.len()).size()
+    }
+
+    #[getter]
+    fn learner(&self) -> PlayerId {
+        self.env.learner()
+    }
+}
+
+#[pymodule]
+fn monopoly_abm(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyBoard>()?;
+    m.add_class::<PyGame>()?;
+    m.add_class::<PyEnv>()?;
+    m.add_function(wrap_pyfunction!(batch, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use pyo3::{ffi::c_str, prelude::*, types::PyDict};
+
+    use crate::{action::Action, agent::Agent, scenario::Scenario, strategy::Strategy};
+
+    use super::{batch, PyAgent, PyBoard, PyGame};
+
+    const BOTS: &std::ffi::CStr = c_str!(
+        r#"
+class First:
+    name = "First"
+
+    def __init__(self):
+        self.requests = 0
+
+    def decide(self, request):
+        self.requests += 1
+        return 0
+
+class Replies:
+    def __init__(self, reply):
+        self.reply = reply
+
+    def decide(self, request):
+        return self.reply
+
+class Raises:
+    def decide(self, request):
+        raise RuntimeError("no idea")
+"#
+    );
+
+    fn bots(py: Python<'_>) -> Bound<'_, PyModule> {
+        PyModule::from_code(py, BOTS, c_str!("bots.py"), c_str!("bots")).unwrap()
+    }
+
+    #[test]
+    fn python_agents_play_games() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let first = bots(py).getattr("First").unwrap().call0().unwrap();
+            let greedy = "Greedy".into_pyobject(py).unwrap().into_any();
+            let mut game = PyGame::new(vec![first.clone(), greedy], None, 1, 50).unwrap();
+            game.play();
+
+            assert!(game.rounds() > 0);
+            assert_eq!(game.game.players[0].name, "First");
+            let requests: usize = first.getattr("requests").unwrap().extract().unwrap();
+            assert!(requests > 0);
+        });
+    }
+
+    #[test]
+    fn bad_replies_fall_back() {
+        pyo3::prepare_freethreaded_python();
+        let mut game = Scenario::new(2).roll(&[1, 2]).build().unwrap();
+        game.apply(Action::Roll).unwrap();
+        let pending = game.pending().unwrap();
+
+        Python::with_gil(|py| {
+            let bots = bots(py);
+            let replies = bots.getattr("Replies").unwrap();
+            let agents = [
+                replies.call1((99,)).unwrap(),
+                replies.call1(("nonsense",)).unwrap(),
+                replies.call1(("PayBail",)).unwrap(),
+                bots.getattr("Raises").unwrap().call0().unwrap(),
+            ];
+            for agent in agents {
+                let mut agent = PyAgent {
+                    agent: agent.unbind(),
+                    fallback: Strategy::Greedy.agent(0),
+                };
+                assert_eq!(agent.decide(&game.view(), &pending), Action::Buy);
+            }
+        });
+    }
+
+    #[test]
+    fn batches_name_the_squares_of_their_board() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let strategies = vec![
+                "Greedy".into_pyobject(py).unwrap().into_any(),
+                "Cautious".into_pyobject(py).unwrap().into_any(),
+            ];
+            let board = Py::new(py, PyBoard::us()).unwrap();
+            let tables = batch(py, strategies, Some(board.borrow(py)), 2, 20, 0).unwrap();
+
+            let tables = tables.downcast_bound::<PyDict>(py).unwrap();
+            let squares = tables.get_item("squares").unwrap().unwrap();
+            let names: Vec<String> = squares.get_item("name").unwrap().extract().unwrap();
+            assert!(names.iter().any(|name| name == "Boardwalk"));
+        });
+    }
+}
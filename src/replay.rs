@@ -0,0 +1,313 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    agent::Agent,
+    error::MonopolyError,
+    event::Event,
+    game::Game,
+    locations::{ColourGroup, SquareKind},
+    player::PlayerId,
+    square::BoardPosition,
+    token::Token,
+};
+
+/// A square as printed on the board
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ReplaySquare {
+    pub name: String,
+    pub kind: SquareKind,
+    pub group: Option<ColourGroup>,
+    pub price: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ReplayPlayer {
+    pub id: PlayerId,
+    pub name: String,
+    pub token: Token,
+}
+
+/// A player at the end of a turn
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PlayerFrame {
+    pub id: PlayerId,
+    pub position: BoardPosition,
+    pub cash: usize,
+    pub net_worth: usize,
+    pub active: bool,
+    pub in_jail: bool,
+}
+
+/// Who owns a square and what stands on it at the end of a turn
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SquareFrame {
+    pub owner: Option<PlayerId>,
+    pub houses: usize,
+    pub hotels: usize,
+    pub mortgaged: bool,
+}
+
+/// The table after one turn, along with everything that happened during it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub round: usize,
+    /// Whose turn is next
+    pub turn: PlayerId,
+    pub players: Vec<PlayerFrame>,
+    pub squares: Vec<SquareFrame>,
+    pub free_parking: usize,
+    pub events: Vec<Event>,
+}
+
+impl Frame {
+    fn new(game: &Game, events: &[Event]) -> Self {
+        Self {
+            round: game.rounds(),
+            turn: game.current_player(),
+            players: game
+                .players
+                .iter()
+                .map(|p| PlayerFrame {
+                    id: p.id,
+                    position: p.current_position(),
+                    cash: p.current_balance(),
+                    net_worth: game.holdings(p).net_worth(),
+                    active: p.is_active(),
+                    in_jail: p.in_jail(),
+                })
+                .collect(),
+            squares: game
+                .board
+                .iter()
+                .map(|sq| {
+                    let (houses, hotels) = sq.buildings();
+                    SquareFrame {
+                        owner: sq.owner_id(),
+                        houses,
+                        hotels,
+                        mortgaged: sq.is_mortgaged(),
+                    }
+                })
+                .collect(),
+            free_parking: game.state.free_parking.borrow().0,
+            events: events.to_vec(),
+        }
+    }
+}
+
+/// A game turn by turn, enough to redraw the table at any point without the engine
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Replay {
+    pub squares: Vec<ReplaySquare>,
+    pub players: Vec<ReplayPlayer>,
+    /// The table before the first roll, then once per turn
+    pub frames: Vec<Frame>,
+    /// Events of the game already in a frame
+    #[serde(skip)]
+    seen: usize,
+}
+
+impl Replay {
+    /// Starts recording `game`, which should have `Game::record_events` on
+    pub fn new(game: &Game) -> Self {
+        let view = game.view();
+        Self {
+            squares: view
+                .squares()
+                .map(|sq| ReplaySquare {
+                    name: sq.name.to_string(),
+                    kind: sq.kind,
+                    group: sq.group,
+                    price: sq.price,
+                })
+                .collect(),
+            players: view
+                .players()
+                .map(|p| ReplayPlayer {
+                    id: p.id,
+                    name: p.name.to_string(),
+                    token: p.token,
+                })
+                .collect(),
+            frames: vec![Frame::new(game, game.events())],
+            seen: game.events().len(),
+        }
+    }
+
+    /// Play the game out turn by turn, stopping after `max_rounds` if it's still going
+    pub fn record(game: &mut Game, agents: &mut [Box<dyn Agent>], max_rounds: usize) -> Self {
+        game.record_events(true);
+        let mut replay = Self::new(game);
+        while game.rounds() < max_rounds && game.play_turn(agents) {
+            replay.push(game);
+        }
+        replay.push(game);
+        replay
+    }
+
+    /// Add a frame for the turn just played
+    pub fn push(&mut self, game: &Game) {
+        let events = &game.events()[self.seen..];
+        self.frames.push(Frame::new(game, events));
+        self.seen = game.events().len();
+    }
+
+    pub fn load(path: &Path) -> Result<Self, MonopolyError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, MonopolyError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), MonopolyError> {
+        Ok(fs::write(path, serde_json::to_string(self)?)?)
+    }
+
+    pub fn player_name(&self, id: PlayerId) -> &str {
+        self.players
+            .iter()
+            .find(|p| p.id == id)
+            .map_or("?", |p| p.name.as_str())
+    }
+
+    fn square_name(&self, position: BoardPosition) -> &str {
+        self.squares
+            .get(position)
+            .map_or("?", |sq| sq.name.as_str())
+    }
+
+    /// One line for the event feed
+    pub fn describe(&self, event: &Event) -> String {
+        let name = |id| self.player_name(id);
+        let square = |position| self.square_name(position);
+        match event {
+            Event::Rolled { player, faces, .. } => {
+                let faces: Vec<String> = faces.iter().map(ToString::to_string).collect();
+                format!("{} rolled {}", name(*player), faces.join(" + "))
+            }
+            Event::Moved { player, to, .. } => {
+                format!("{} moved to {}", name(*player), square(*to))
+            }
+            Event::PassedGo { player } => format!("{} passed Go", name(*player)),
+            Event::Card { player, kind, card } => {
+                let pile = match kind {
+                    SquareKind::Chance => "Chance",
+                    _ => "Community Chest",
+                };
+                format!("{} drew {} card {}", name(*player), pile, card)
+            }
+            Event::Rent {
+                player,
+                owner,
+                amount,
+            } => format!("{} paid {} rent to {}", name(*player), amount, name(*owner)),
+            Event::Bought {
+                player,
+                position,
+                price,
+            } => format!(
+                "{} bought {} for {}",
+                name(*player),
+                square(*position),
+                price
+            ),
+            Event::Built { player, position } => {
+                format!("{} built on {}", name(*player), square(*position))
+            }
+            Event::SoldBuilding {
+                player,
+                position,
+                refund,
+            } => format!(
+                "{} sold a building on {} for {}",
+                name(*player),
+                square(*position),
+                refund
+            ),
+            Event::Mortgaged { player, position } => {
+                format!("{} mortgaged {}", name(*player), square(*position))
+            }
+            Event::Unmortgaged { player, position } => {
+                format!(
+                    "{} lifted the mortgage on {}",
+                    name(*player),
+                    square(*position)
+                )
+            }
+            Event::Traded { offer } => {
+                format!("{} traded with {}", name(offer.from), name(offer.to))
+            }
+            Event::Jailed { player } => format!("{} went to jail", name(*player)),
+            Event::Released { player } => format!("{} left jail", name(*player)),
+            Event::Bankrupt { player, creditor } => format!(
+                "{} went bankrupt to {}",
+                name(*player),
+                creditor.map_or("the bank", name)
+            ),
+            Event::GameOver { winner: Some(id) } => format!("Game over, {} wins", name(*id)),
+            Event::GameOver { winner: None } => "Game over".to_string(),
+        }
+    }
+}
+
+/// Squares along each side of the ring, the corner that ends a side included
+pub const fn ring_side(board_size: usize) -> usize {
+    board_size.div_ceil(4)
+}
+
+/// Column and row of a square on a ring `ring_side + 1` squares across. Go is in the
+/// bottom right corner and play runs clockwise, as on the printed board.
+pub const fn ring_cell(position: BoardPosition, board_size: usize) -> (usize, usize) {
+    let side = ring_side(board_size);
+    let last = side;
+    let offset = position % side;
+    match position / side {
+        0 => (last - offset, last),
+        1 => (0, last - offset),
+        2 => (offset, 0),
+        _ => (last, offset),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{event::Event, game::Game, strategy::Strategy};
+
+    use super::{ring_cell, Replay};
+
+    #[test]
+    fn every_turn_is_a_frame() {
+        let mut game = Game::new(3, 4);
+        let mut agents: Vec<_> = (0..3).map(|s| Strategy::Greedy.agent(s)).collect();
+        let replay = Replay::record(&mut game, &mut agents, 20);
+
+        let last = replay.frames.last().unwrap();
+        assert_eq!(last.round, game.rounds());
+        assert_eq!(last.players[0].cash, game.players[0].current_balance());
+        let events: usize = replay.frames.iter().map(|f| f.events.len()).sum();
+        assert_eq!(events, game.events().len());
+        assert!(matches!(
+            replay.frames[1].events[0],
+            Event::Rolled { player: 1, .. }
+        ));
+
+        let json = serde_json::to_string(&replay).unwrap();
+        assert_eq!(Replay::from_json(&json).unwrap().frames, replay.frames);
+        assert!(replay
+            .describe(&replay.frames[1].events[0])
+            .starts_with("Player 1 rolled"));
+    }
+
+    #[test]
+    fn the_ring_runs_clockwise_from_go() {
+        assert_eq!(ring_cell(0, 40), (10, 10));
+        assert_eq!(ring_cell(1, 40), (9, 10));
+        assert_eq!(ring_cell(10, 40), (0, 10));
+        assert_eq!(ring_cell(20, 40), (0, 0));
+        assert_eq!(ring_cell(30, 40), (10, 0));
+        assert_eq!(ring_cell(39, 40), (10, 9));
+    }
+}
@@ -0,0 +1,340 @@
+use std::{fmt, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::external::{ExternalAgent, ExternalConfig};
+use crate::{
+    agent::Agent,
+    config::BoardConfig,
+    error::MonopolyError,
+    game::Game,
+    monopoly::MonopolyState,
+    player::{Player, STARTING_CASH},
+    strategy::Strategy,
+    token::Token,
+};
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 8;
+
+const fn default_strategy() -> Strategy {
+    Strategy::Greedy
+}
+
+const fn default_cash() -> usize {
+    STARTING_CASH
+}
+
+/// One entry of a roster. Anything left out is filled in when the table is seated.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct PlayerConfig {
+    pub name: String,
+    /// The first token nobody else asked for when not given
+    #[serde(default)]
+    pub token: Option<Token>,
+    /// Also the fallback when `external` is set
+    #[serde(default = "default_strategy")]
+    pub strategy: Strategy,
+    /// Played by a program of its own over stdin and stdout, not on wasm32
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(default)]
+    pub external: Option<ExternalConfig>,
+    #[serde(default = "default_cash")]
+    pub cash: usize,
+    /// 0 plays first. Players without a seat fill the gaps in the order listed.
+    #[serde(default)]
+    pub seat: Option<usize>,
+}
+
+impl PlayerConfig {
+    pub fn new(name: &str, strategy: Strategy) -> Self {
+        Self {
+            name: name.to_string(),
+            token: None,
+            strategy,
+            #[cfg(not(target_arch = "wasm32"))]
+            external: None,
+            cash: STARTING_CASH,
+            seat: None,
+        }
+    }
+
+    pub const fn with_token(mut self, token: Token) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    pub const fn with_cash(mut self, cash: usize) -> Self {
+        self.cash = cash;
+        self
+    }
+
+    pub const fn with_seat(mut self, seat: usize) -> Self {
+        self.seat = Some(seat);
+        self
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_external(mut self, external: ExternalConfig) -> Self {
+        self.external = Some(external);
+        self
+    }
+
+    pub fn agent(&self, seed: u64) -> Result<Box<dyn Agent>, RosterError> {
+        let fallback = self.strategy.agent(seed);
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(config) = &self.external {
+            return match ExternalAgent::spawn(config, fallback) {
+                Ok(agent) => Ok(Box::new(agent)),
+                Err(e) => Err(RosterError::CannotStart {
+                    name: self.name.clone(),
+                    error: e.to_string(),
+                }),
+            };
+        }
+        Ok(fallback)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RosterError {
+    TooFewPlayers(usize),
+    TooManyPlayers(usize),
+    DuplicateToken(Token),
+    SeatTaken(usize),
+    SeatOutOfRange {
+        seat: usize,
+        players: usize,
+    },
+    /// An external bot's program couldn't be run
+    CannotStart {
+        name: String,
+        error: String,
+    },
+}
+
+impl fmt::Display for RosterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewPlayers(n) => write!(
+                f,
+                "{} player(s) listed, at least {} are needed",
+                n, MIN_PLAYERS
+            ),
+            Self::TooManyPlayers(n) => {
+                write!(f, "{} players listed, at most {} can play", n, MAX_PLAYERS)
+            }
+            Self::DuplicateToken(token) => {
+                write!(f, "{:?} is chosen by more than one player", token)
+            }
+            Self::SeatTaken(seat) => write!(f, "Seat {} is given to more than one player", seat),
+            Self::SeatOutOfRange { seat, players } => write!(
+                f,
+                "Seat {} does not exist with {} players (seats start at 0)",
+                seat, players
+            ),
+            Self::CannotStart { name, error } => {
+                write!(f, "Couldn't start the bot for {}: {}", name, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RosterError {}
+
+/// Who is playing, and in which order
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct Roster {
+    pub players: Vec<PlayerConfig>,
+}
+
+impl Roster {
+    pub fn new(players: Vec<PlayerConfig>) -> Self {
+        Self { players }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, MonopolyError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, MonopolyError> {
+        let roster: Self = serde_json::from_str(json)?;
+        roster.seated()?;
+        Ok(roster)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), MonopolyError> {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    /// Entries in turn order with every token filled in
+    pub fn seated(&self) -> Result<Vec<PlayerConfig>, RosterError> {
+        let n = self.players.len();
+        if n < MIN_PLAYERS {
+            return Err(RosterError::TooFewPlayers(n));
+        }
+        if n > MAX_PLAYERS {
+            return Err(RosterError::TooManyPlayers(n));
+        }
+
+        let mut seats: Vec<Option<PlayerConfig>> = vec![None; n];
+        for entry in self.players.iter().filter(|p| p.seat.is_some()) {
+            let seat = entry.seat.unwrap_or_default();
+            match seats.get_mut(seat) {
+                None => return Err(RosterError::SeatOutOfRange { seat, players: n }),
+                Some(Some(_)) => return Err(RosterError::SeatTaken(seat)),
+                Some(slot) => *slot = Some(entry.clone()),
+            }
+        }
+        let mut unseated = self.players.iter().filter(|p| p.seat.is_none());
+        for slot in seats.iter_mut().filter(|s| s.is_none()) {
+            *slot = unseated.next().cloned();
+        }
+        let mut seated: Vec<PlayerConfig> = seats.into_iter().flatten().collect();
+
+        let mut taken = vec![];
+        for token in seated.iter().filter_map(|p| p.token) {
+            if taken.contains(&token) {
+                return Err(RosterError::DuplicateToken(token));
+            }
+            taken.push(token);
+        }
+        for (seat, entry) in seated.iter_mut().enumerate() {
+            entry.seat = Some(seat);
+            if entry.token.is_none() {
+                let token = Token::ALL
+                    .iter()
+                    .copied()
+                    .find(|t| !taken.contains(t))
+                    .unwrap_or(Token::Boot);
+                taken.push(token);
+                entry.token = Some(token);
+            }
+        }
+
+        Ok(seated)
+    }
+
+    /// Players in turn order, numbered from 1
+    pub fn create_players(&self) -> Result<Vec<Player>, RosterError> {
+        Ok(self
+            .seated()?
+            .iter()
+            .enumerate()
+            .map(|(seat, p)| {
+                Player::with_details(seat + 1, &p.name, p.token.unwrap_or(Token::Boot), p.cash)
+            })
+            .collect())
+    }
+
+    /// An agent for every seat, in the same order as `create_players`
+    pub fn agents(&self, seed: u64) -> Result<Vec<Box<dyn Agent>>, RosterError> {
+        self.seated()?
+            .iter()
+            .enumerate()
+            .map(|(seat, p)| p.agent(seed.wrapping_add(seat as u64)))
+            .collect()
+    }
+
+    /// A new game on `board` along with the agents to play it
+    pub fn game(
+        &self,
+        board: &BoardConfig,
+        seed: u64,
+    ) -> Result<(Game, Vec<Box<dyn Agent>>), RosterError> {
+        let game = Game::with_board(self.create_players()?, board, MonopolyState::seeded(seed));
+        Ok((game, self.agents(seed)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{strategy::Strategy, token::Token};
+
+    use super::{PlayerConfig, Roster, RosterError};
+
+    #[test]
+    fn seats_and_tokens_are_filled_in() {
+        let roster = Roster::new(vec![
+            PlayerConfig::new("Ada", Strategy::Greedy),
+            PlayerConfig::new("Bob", Strategy::Random).with_token(Token::Iron),
+            PlayerConfig::new("Cy", Strategy::Cautious)
+                .with_seat(0)
+                .with_cash(2000),
+        ]);
+
+        let players = roster.create_players().unwrap();
+        let names: Vec<_> = players.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Cy", "Ada", "Bob"]);
+        assert_eq!(players[0].current_balance(), 2000);
+        assert_eq!(players[2].token, Token::Iron);
+        assert_eq!(players[0].token, Token::Terrier);
+        assert_eq!(players[1].token, Token::Horse);
+        assert_eq!(roster.agents(1).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn invalid_rosters_are_rejected() {
+        let players = |n| {
+            (0..n)
+                .map(|_| PlayerConfig::new("P", Strategy::Greedy))
+                .collect()
+        };
+        assert_eq!(
+            Roster::new(players(1)).seated(),
+            Err(RosterError::TooFewPlayers(1))
+        );
+        assert_eq!(
+            Roster::new(players(9)).seated(),
+            Err(RosterError::TooManyPlayers(9))
+        );
+        assert!(Roster::new(players(10)).create_players().is_err());
+        assert_eq!(Roster::new(players(8)).create_players().unwrap().len(), 8);
+
+        let mut clash = Roster::new(players(2));
+        clash.players[0].token = Some(Token::Car);
+        clash.players[1].token = Some(Token::Car);
+        assert_eq!(clash.seated(), Err(RosterError::DuplicateToken(Token::Car)));
+
+        clash.players[1].token = None;
+        clash.players[0].seat = Some(2);
+        assert_eq!(
+            clash.seated(),
+            Err(RosterError::SeatOutOfRange {
+                seat: 2,
+                players: 2
+            })
+        );
+    }
+
+    #[test]
+    fn rosters_load_from_json() {
+        let json = r#"{"players": [
+            {"name": "Ada", "token": "Boot", "strategy": "Cautious"},
+            {"name": "Bob", "cash": 1000, "seat": 0}
+        ]}"#;
+        let roster = Roster::from_json(json).unwrap();
+        let seated = roster.seated().unwrap();
+        assert_eq!(seated[0].name, "Bob");
+        assert_eq!(seated[0].strategy, Strategy::Greedy);
+        assert_eq!(seated[1].token, Some(Token::Boot));
+
+        assert!(Roster::from_json(r#"{"players": [{"name": "Solo"}]}"#).is_err());
+    }
+
+    #[test]
+    fn external_bots_are_started_with_the_roster() {
+        let json = r#"{"players": [
+            {"name": "Ada", "external": {"command": ["sh", "-c", "cat > /dev/null"]}},
+            {"name": "Bob", "external": {"command": ["no-such-bot"], "timeout_ms": 50}}
+        ]}"#;
+        let roster = Roster::from_json(json).unwrap();
+        assert_eq!(roster.players[1].external.as_ref().unwrap().timeout_ms, 50);
+        assert!(roster.players[0].agent(0).is_ok());
+        assert!(matches!(
+            roster.agents(0),
+            Err(RosterError::CannotStart { name, .. }) if name == "Bob"
+        ));
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use crate::termination::EndConditions;
+
+/// How a square with a `tax_rate` is charged
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum IncomeTax {
+    /// The player picks the flat amount or the percentage of their net worth
+    Choice,
+    /// Modern editions only ever charge the flat amount
+    Flat,
+}
+
+/// House rules and edition differences that aren't part of the board
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct Rules {
+    pub income_tax: IncomeTax,
+    #[serde(default)]
+    pub end: EndConditions,
+}
+
+impl Rules {
+    /// The original rules, with the Income Tax choice
+    pub const fn classic() -> Self {
+        Self {
+            income_tax: IncomeTax::Choice,
+            end: EndConditions::last_standing(),
+        }
+    }
+
+    pub const fn modern() -> Self {
+        Self {
+            income_tax: IncomeTax::Flat,
+            end: EndConditions::last_standing(),
+        }
+    }
+
+    pub const fn with_end(mut self, end: EndConditions) -> Self {
+        self.end = end;
+        self
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        action::{Action, Decision},
+        scenario::Scenario,
+    };
+
+    use super::Rules;
+
+    #[test]
+    fn income_tax_choice_follows_the_rules() {
+        let scenario = Scenario::new(2).cash(1, 1000).owner(1, 1).roll(&[1, 3]);
+
+        let mut game = scenario.clone().build().unwrap();
+        game.apply(Action::Roll).unwrap();
+        assert_eq!(
+            game.pending().unwrap().decision,
+            Decision::IncomeTax {
+                flat: 200,
+                percentage: 106
+            }
+        );
+        game.apply(Action::PayTaxPercentage).unwrap();
+        assert_eq!(game.player(1).unwrap().current_balance(), 894);
+        assert_eq!(game.state.free_parking.borrow().0, 106);
+
+        let mut game = scenario.rules(Rules::modern()).build().unwrap();
+        game.apply(Action::Roll).unwrap();
+        assert_eq!(game.pending().unwrap().decision, Decision::Manage);
+        assert_eq!(game.player(1).unwrap().current_balance(), 800);
+    }
+}
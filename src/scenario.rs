@@ -0,0 +1,560 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action::Action,
+    config::BoardConfig,
+    dice::ScriptedDice,
+    error::MonopolyError,
+    event::Event,
+    game::Game,
+    locations::SquareKind,
+    monopoly::{Count, MonopolyState},
+    player::{Player, PlayerId, STARTING_CASH},
+    rules::Rules,
+    square::{BoardPosition, PropertyState},
+};
+
+fn default_board() -> String {
+    "uk".to_string()
+}
+
+const fn default_turn() -> PlayerId {
+    1
+}
+
+const fn default_turns() -> usize {
+    1
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ScenarioPlayer {
+    pub cash: usize,
+    #[serde(default)]
+    pub position: BoardPosition,
+    /// Turns already spent in jail
+    #[serde(default)]
+    pub jail: Option<usize>,
+    #[serde(default)]
+    pub jail_card: bool,
+}
+
+impl Default for ScenarioPlayer {
+    fn default() -> Self {
+        Self {
+            cash: STARTING_CASH,
+            position: 0,
+            jail: None,
+            jail_card: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct ScenarioProperty {
+    pub owner: PlayerId,
+    /// 5 is a hotel
+    #[serde(default)]
+    pub houses: usize,
+    #[serde(default)]
+    pub mortgaged: bool,
+}
+
+/// What the game should look like once the scenario has run. Only the entries
+/// listed are checked.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct Expect {
+    #[serde(default)]
+    pub cash: BTreeMap<PlayerId, usize>,
+    #[serde(default)]
+    pub position: BTreeMap<PlayerId, BoardPosition>,
+    /// `None` for the bank
+    #[serde(default)]
+    pub owner: BTreeMap<BoardPosition, Option<PlayerId>>,
+    #[serde(default)]
+    pub houses: BTreeMap<BoardPosition, usize>,
+    #[serde(default)]
+    pub in_jail: BTreeMap<PlayerId, bool>,
+    #[serde(default)]
+    pub active: BTreeMap<PlayerId, bool>,
+    /// Must all appear in the event log, in this order
+    #[serde(default)]
+    pub events: Vec<Event>,
+}
+
+/// A mid-game position plus scripted dice, cards and decisions. Players are
+/// numbered from 1 in seat order. Decisions not scripted get the do-nothing choice.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Scenario {
+    /// A built-in board name or the path of a board file
+    #[serde(default = "default_board")]
+    pub board: String,
+    pub players: Vec<ScenarioPlayer>,
+    #[serde(default)]
+    pub properties: BTreeMap<BoardPosition, ScenarioProperty>,
+    /// Player to move first
+    #[serde(default = "default_turn")]
+    pub turn: PlayerId,
+    #[serde(default)]
+    pub free_parking: usize,
+    #[serde(default)]
+    pub rules: Rules,
+    /// Faces of each roll in order, real rolls follow once they run out
+    #[serde(default)]
+    pub dice: Vec<Vec<usize>>,
+    #[serde(default)]
+    pub chance: Vec<usize>,
+    #[serde(default)]
+    pub community_chest: Vec<usize>,
+    #[serde(default)]
+    pub decisions: Vec<Action>,
+    #[serde(default = "default_turns")]
+    pub turns: usize,
+    #[serde(default)]
+    pub seed: u64,
+    #[serde(default)]
+    pub expect: Expect,
+    /// Ids given to the builder that aren't in `players`, reported by `build`
+    #[serde(skip)]
+    unknown_players: Vec<PlayerId>,
+}
+
+impl Scenario {
+    pub fn new(n_players: usize) -> Self {
+        Self {
+            board: default_board(),
+            players: vec![ScenarioPlayer::default(); n_players],
+            properties: BTreeMap::new(),
+            turn: default_turn(),
+            free_parking: 0,
+            rules: Rules::default(),
+            dice: vec![],
+            chance: vec![],
+            community_chest: vec![],
+            decisions: vec![],
+            turns: default_turns(),
+            seed: 0,
+            expect: Expect::default(),
+            unknown_players: vec![],
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, MonopolyError> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn board(mut self, name_or_path: &str) -> Self {
+        self.board = name_or_path.to_string();
+        self
+    }
+
+    fn set_player(&mut self, id: PlayerId, set: impl FnOnce(&mut ScenarioPlayer)) {
+        match id
+            .checked_sub(1)
+            .and_then(|seat| self.players.get_mut(seat))
+        {
+            Some(player) => set(player),
+            None => self.unknown_players.push(id),
+        }
+    }
+
+    pub fn cash(mut self, id: PlayerId, cash: usize) -> Self {
+        self.set_player(id, |p| p.cash = cash);
+        self
+    }
+
+    pub fn position(mut self, id: PlayerId, position: BoardPosition) -> Self {
+        self.set_player(id, |p| p.position = position);
+        self
+    }
+
+    pub fn in_jail(mut self, id: PlayerId, turns_served: usize) -> Self {
+        self.set_player(id, |p| p.jail = Some(turns_served));
+        self
+    }
+
+    pub fn jail_card(mut self, id: PlayerId) -> Self {
+        self.set_player(id, |p| p.jail_card = true);
+        self
+    }
+
+    pub fn owner(mut self, position: BoardPosition, id: PlayerId) -> Self {
+        self.properties.entry(position).or_default().owner = id;
+        self
+    }
+
+    pub fn houses(mut self, position: BoardPosition, houses: usize) -> Self {
+        self.properties.entry(position).or_default().houses = houses;
+        self
+    }
+
+    pub fn mortgaged(mut self, position: BoardPosition) -> Self {
+        self.properties.entry(position).or_default().mortgaged = true;
+        self
+    }
+
+    pub const fn turn(mut self, id: PlayerId) -> Self {
+        self.turn = id;
+        self
+    }
+
+    pub const fn free_parking(mut self, amount: usize) -> Self {
+        self.free_parking = amount;
+        self
+    }
+
+    pub fn roll(mut self, faces: &[usize]) -> Self {
+        self.dice.push(faces.to_vec());
+        self
+    }
+
+    pub fn chance(mut self, card: usize) -> Self {
+        self.chance.push(card);
+        self
+    }
+
+    pub fn community_chest(mut self, card: usize) -> Self {
+        self.community_chest.push(card);
+        self
+    }
+
+    pub const fn rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn decide(mut self, action: Action) -> Self {
+        self.decisions.push(action);
+        self
+    }
+
+    pub const fn turns(mut self, turns: usize) -> Self {
+        self.turns = turns;
+        self
+    }
+
+    /// The game set up at the start of the scenario, waiting on its first decision
+    pub fn build(&self) -> Result<Game, MonopolyError> {
+        let invalid = |reason: String| Err(MonopolyError::InvalidScenario(reason));
+        let playing = 1..=self.players.len();
+        if let Some(id) = self.unknown_players.first() {
+            return invalid(format!("Player {} isn't playing", id));
+        }
+        if !playing.contains(&self.turn) {
+            return invalid(format!("Player {} to move isn't playing", self.turn));
+        }
+
+        let board = BoardConfig::load(&self.board)?;
+        let players: Vec<Player> = (1..=self.players.len()).map(Player::new).collect();
+        let mut state = MonopolyState::seeded(self.seed).with_rules(self.rules);
+        state.free_parking.replace(Count(self.free_parking));
+        state.stack_cards(SquareKind::Chance, &self.chance);
+        state.stack_cards(SquareKind::CommunityChest, &self.community_chest);
+        let rolls: Vec<&[usize]> = self.dice.iter().map(Vec::as_slice).collect();
+
+        let mut game = Game::setup(
+            players,
+            &board,
+            state,
+            Box::new(ScriptedDice::from_faces(&rolls)),
+        );
+        game.record_events(true);
+
+        for (player, setup) in game.players.iter().zip(&self.players) {
+            if setup.position >= game.board.len() {
+                return invalid(format!(
+                    "Player {} is on square {}, the board has {}",
+                    player.id,
+                    setup.position,
+                    game.board.len()
+                ));
+            }
+            let mut s = player.state.borrow_mut();
+            s.balance = setup.cash;
+            s.current_position = setup.position;
+            s.jail = setup.jail;
+            s.jail_card = setup.jail_card;
+        }
+        for (&position, setup) in &self.properties {
+            match game.board.get(position) {
+                Some(sq) if sq.is_ownable() => {}
+                _ => return invalid(format!("Square {} can't be owned", position)),
+            }
+            if !playing.contains(&setup.owner) {
+                return invalid(format!(
+                    "Square {} owner {} isn't playing",
+                    position, setup.owner
+                ));
+            }
+            if setup.houses > 5 {
+                return invalid(format!(
+                    "Square {} can't have {} houses",
+                    position, setup.houses
+                ));
+            }
+            if setup.houses > 0
+                && (game.board[position].kind() != SquareKind::Street || setup.mortgaged)
+            {
+                return invalid(format!("Square {} can't be built on", position));
+            }
+
+            game.board[position].set_property_state(PropertyState {
+                ownable: true,
+                owner: Some(setup.owner),
+                house_count: setup.houses,
+                hotel_count: 0,
+                mortgaged: setup.mortgaged,
+            });
+        }
+
+        game.set_turn(self.turn)?;
+        game.start()?;
+        Ok(game)
+    }
+
+    /// Play `turns` turns, feeding in the scripted decisions
+    pub fn run(&self) -> Result<Game, MonopolyError> {
+        let mut game = self.build()?;
+        let mut decisions = self.decisions.iter().cloned();
+        let mut turns = 0;
+        let mut turn = game.current_player();
+
+        while let Some(pending) = game.pending() {
+            if game.current_player() != turn {
+                turns += 1;
+                turn = game.current_player();
+            }
+            if turns >= self.turns {
+                break;
+            }
+
+            let action = match decisions.next() {
+                Some(action) => action,
+                None => {
+                    log::info!(
+                        "Unscripted {:?} [Player={}]",
+                        pending.decision,
+                        pending.player
+                    );
+                    game.legal_actions().swap_remove(0)
+                }
+            };
+            game.apply(action)?;
+        }
+
+        Ok(game)
+    }
+
+    /// Run the scenario and check the game against `expect`, listing every difference
+    pub fn verify(&self) -> Result<Game, MonopolyError> {
+        let game = self.run()?;
+        let failures = self.expect.failures(&game);
+        if failures.is_empty() {
+            Ok(game)
+        } else {
+            Err(MonopolyError::ScenarioFailed(failures))
+        }
+    }
+}
+
+impl Expect {
+    pub fn failures(&self, game: &Game) -> Vec<String> {
+        let mut failures = vec![];
+        // A player who isn't in the game is reported in place of the value
+        let mut check = |what: String, expected: String, actual: Result<String, MonopolyError>| {
+            let actual = actual.unwrap_or_else(|e| e.to_string());
+            if expected != actual {
+                failures.push(format!("{}: expected {}, found {}", what, expected, actual));
+            }
+        };
+        for &position in self.owner.keys().chain(self.houses.keys()) {
+            if position >= game.board.len() {
+                return vec![format!("Square {} isn't on the board", position)];
+            }
+        }
+
+        for (&id, cash) in &self.cash {
+            let actual = game.player(id).map(|p| p.current_balance().to_string());
+            check(format!("Player {} cash", id), cash.to_string(), actual);
+        }
+        for (&id, position) in &self.position {
+            let actual = game.player(id).map(|p| p.current_position().to_string());
+            check(
+                format!("Player {} position", id),
+                position.to_string(),
+                actual,
+            );
+        }
+        for (&id, jailed) in &self.in_jail {
+            let actual = game.player(id).map(|p| p.in_jail().to_string());
+            check(format!("Player {} in jail", id), jailed.to_string(), actual);
+        }
+        for (&id, active) in &self.active {
+            let actual = game.player(id).map(|p| p.is_active().to_string());
+            check(format!("Player {} active", id), active.to_string(), actual);
+        }
+        for (&position, owner) in &self.owner {
+            let actual = game.board[position].owner_id();
+            check(
+                format!("Square {} owner", position),
+                format!("{:?}", owner),
+                Ok(format!("{:?}", actual)),
+            );
+        }
+        for (&position, houses) in &self.houses {
+            let actual = game.board[position].house_count();
+            check(
+                format!("Square {} houses", position),
+                houses.to_string(),
+                Ok(actual.to_string()),
+            );
+        }
+
+        let mut log = game.events().iter();
+        for event in &self.events {
+            if !log.any(|e| e == event) {
+                failures.push(format!("Event {:?} not found in order", event));
+                break;
+            }
+        }
+        failures
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::Path};
+
+    use crate::{action::Action, error::MonopolyError, event::Event, locations::SquareKind};
+
+    use super::Scenario;
+
+    #[test]
+    fn rent_is_paid_to_the_owner() {
+        // Whitechapel Road with 2 houses charges 60 on the official rent table
+        let game = Scenario::new(2)
+            .owner(1, 2)
+            .owner(3, 2)
+            .houses(3, 2)
+            .roll(&[1, 2])
+            .run()
+            .unwrap();
+
+        assert_eq!(game.player(1).unwrap().current_balance(), 1440);
+        assert_eq!(game.player(2).unwrap().current_balance(), 1560);
+        assert!(game.events().contains(&Event::Rent {
+            player: 1,
+            owner: 2,
+            amount: 60
+        }));
+
+        // Two stations charge 50, both utilities ten times the roll of 12
+        let rent = |scenario: Scenario| {
+            let game = scenario.run().unwrap();
+            game.events().iter().find_map(|e| match e {
+                Event::Rent { amount, .. } => Some(*amount),
+                _ => None,
+            })
+        };
+        let stations = Scenario::new(2).owner(5, 2).owner(15, 2).roll(&[2, 3]);
+        assert_eq!(rent(stations), Some(50));
+        let utilities = Scenario::new(2).owner(12, 2).owner(28, 2).roll(&[5, 7]);
+        assert_eq!(rent(utilities), Some(120));
+    }
+
+    #[test]
+    fn stacked_cards_are_drawn_first() {
+        // Chance 2 is "Go to jail"
+        let game = Scenario::new(2)
+            .position(1, 4)
+            .roll(&[1, 2])
+            .chance(2)
+            .run()
+            .unwrap();
+
+        assert!(game.player(1).unwrap().in_jail());
+        assert_eq!(game.player(1).unwrap().current_position(), 10);
+        assert!(game.events().contains(&Event::Card {
+            player: 1,
+            kind: SquareKind::Chance,
+            card: 2
+        }));
+    }
+
+    #[test]
+    fn scripted_decisions_are_applied() {
+        let game = Scenario::new(3)
+            .turn(2)
+            .cash(2, 100)
+            .roll(&[1, 2])
+            .decide(Action::Roll)
+            .decide(Action::Buy)
+            .decide(Action::EndTurn)
+            .roll(&[3, 5])
+            .turns(2)
+            .run()
+            .unwrap();
+
+        assert_eq!(game.board[3].owner_id(), Some(2));
+        assert_eq!(game.player(2).unwrap().current_balance(), 40);
+        assert_eq!(game.player(3).unwrap().current_position(), 8);
+        assert_eq!(game.board[8].owner_id(), None);
+
+        let illegal = Scenario::new(2).decide(Action::AcceptTrade).run();
+        assert!(illegal.is_err());
+    }
+
+    #[test]
+    fn players_not_in_the_scenario_are_reported() {
+        for scenario in [
+            Scenario::new(2).cash(3, 100),
+            Scenario::new(2).in_jail(0, 1),
+            Scenario::new(2).turn(5),
+            Scenario::new(2).owner(1, 3),
+        ] {
+            assert!(matches!(
+                scenario.build(),
+                Err(MonopolyError::InvalidScenario(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn impossible_setups_are_reported() {
+        for scenario in [
+            Scenario::new(2).position(1, 40),
+            Scenario::new(2).owner(40, 1),
+            Scenario::new(2).owner(1, 1).houses(1, 6),
+            Scenario::new(2).owner(5, 1).houses(5, 1),
+            Scenario::new(2).owner(1, 1).houses(1, 1).mortgaged(1),
+        ] {
+            assert!(matches!(
+                scenario.build(),
+                Err(MonopolyError::InvalidScenario(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn expectations_off_the_board_fail() {
+        let mut scenario = Scenario::new(2);
+        scenario.expect.houses.insert(40, 0);
+        assert!(matches!(
+            scenario.verify(),
+            Err(MonopolyError::ScenarioFailed(failures)) if failures == ["Square 40 isn't on the board"]
+        ));
+    }
+
+    #[test]
+    fn scenario_files_pass() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("scenarios");
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let scenario = Scenario::load(&path).unwrap();
+            if let Err(e) = scenario.verify() {
+                panic!("{}: {}", path.display(), e);
+            }
+        }
+    }
+}
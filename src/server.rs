@@ -0,0 +1,808 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hasher},
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    action::{Action, PendingDecision},
+    config::BoardConfig,
+    error::MonopolyError,
+    event::Event,
+    external::{Answer, Request},
+    game::Game,
+    player::PlayerId,
+    roster::{PlayerConfig, Roster},
+    strategy::Strategy,
+    termination::{EndConditions, EndReason},
+};
+
+/// One JSON line from a client
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum ClientMessage {
+    /// Take the first free seat, or `seat` if it's free
+    Join {
+        name: String,
+        seat: Option<usize>,
+    },
+    /// Take a seat back after losing the connection
+    Rejoin {
+        key: String,
+    },
+    Spectate,
+    /// Answer a prompt the way an external bot would, echoing its `seq`
+    Answer(Answer),
+}
+
+/// One JSON line to a client
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum ServerMessage {
+    /// `key` is needed to `Rejoin`
+    Welcome {
+        seat: usize,
+        player: PlayerId,
+        key: String,
+    },
+    /// Seats still free before the game starts
+    Lobby {
+        free: Vec<usize>,
+    },
+    /// An `external::Request` for the seat to answer. Only an answer with its `seq` counts.
+    Prompt(Value),
+    /// Everything that happened since the last `Events`
+    Events(Vec<Event>),
+    /// `view::TableState`
+    State(Value),
+    Rejected(String),
+    GameOver {
+        winner: Option<PlayerId>,
+        end_reason: Option<EndReason>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// One per seat in turn order: `None` for a seat played over the network, otherwise
+    /// the bot that takes it
+    pub seats: Vec<Option<Strategy>>,
+    pub board: BoardConfig,
+    pub seed: u64,
+    pub end: EndConditions,
+    /// How long a network seat has to answer, or to reconnect, before `fallback` plays for it
+    pub timeout: Duration,
+    pub fallback: Strategy,
+}
+
+impl ServerConfig {
+    pub fn new(seats: Vec<Option<Strategy>>) -> Self {
+        Self {
+            seats,
+            board: BoardConfig::uk(),
+            seed: 0,
+            end: EndConditions::last_standing().with_max_rounds(1000),
+            timeout: Duration::from_secs(60),
+            fallback: Strategy::Greedy,
+        }
+    }
+
+    pub fn with_board(mut self, board: BoardConfig) -> Self {
+        self.board = board;
+        self
+    }
+
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub const fn with_end(mut self, end: EndConditions) -> Self {
+        self.end = end;
+        self
+    }
+
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+type ConnId = usize;
+
+/// A client that takes longer than this to read a message is dropped
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What the connection threads pass to the game
+enum Incoming {
+    Connected(ConnId, TcpStream),
+    Message(ConnId, ClientMessage),
+    Unreadable(ConnId, String),
+    Disconnected(ConnId),
+}
+
+#[derive(Debug, Default)]
+struct Seat {
+    remote: bool,
+    name: Option<String>,
+    key: Option<String>,
+    conn: Option<ConnId>,
+    /// Missed a prompt, so is played for without waiting until it's heard from again
+    away: bool,
+}
+
+/// Hosts one game. Clients send and receive JSON lines: they join a seat in the lobby,
+/// are prompted for each of their decisions and see every event as it happens.
+pub struct Server {
+    listener: TcpListener,
+    config: ServerConfig,
+}
+
+impl Server {
+    pub fn bind(addr: impl ToSocketAddrs, config: ServerConfig) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            config,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Wait for every network seat to be taken, then play the game out
+    pub fn run(self) -> Result<Game, MonopolyError> {
+        let (sender, inbox) = mpsc::channel();
+        let listener = self.listener;
+        thread::spawn(move || accept(listener, sender));
+
+        let seats = self
+            .config
+            .seats
+            .iter()
+            .map(|bot| Seat {
+                remote: bot.is_none(),
+                ..Seat::default()
+            })
+            .collect();
+        let mut table = Table {
+            config: self.config,
+            inbox,
+            conns: HashMap::new(),
+            seats,
+            spectators: vec![],
+            prompts: 0,
+        };
+        table.lobby();
+        table.play()
+    }
+}
+
+fn accept(listener: TcpListener, sender: Sender<Incoming>) {
+    for (id, stream) in listener.incoming().enumerate() {
+        let Ok(stream) = stream else { continue };
+        // Prompts and answers are small and go back and forth
+        let _ = stream.set_nodelay(true);
+        let Ok(reader) = stream.try_clone() else {
+            continue;
+        };
+        if sender.send(Incoming::Connected(id, stream)).is_err() {
+            break;
+        }
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines().map_while(Result::ok) {
+                let incoming = match serde_json::from_str(&line) {
+                    Ok(message) => Incoming::Message(id, message),
+                    Err(_) => Incoming::Unreadable(id, line),
+                };
+                if sender.send(incoming).is_err() {
+                    return;
+                }
+            }
+            let _ = sender.send(Incoming::Disconnected(id));
+        });
+    }
+}
+
+/// A seat whose client answered, or came back
+enum Handled {
+    Answer(usize, ConnId, Answer),
+    Rejoined(usize),
+}
+
+struct Table {
+    config: ServerConfig,
+    inbox: Receiver<Incoming>,
+    conns: HashMap<ConnId, TcpStream>,
+    seats: Vec<Seat>,
+    spectators: Vec<ConnId>,
+    /// Of the last prompt sent
+    prompts: u64,
+}
+
+impl Table {
+    fn free_seats(&self) -> Vec<usize> {
+        (0..self.seats.len())
+            .filter(|&s| self.seats[s].remote && self.seats[s].name.is_none())
+            .collect()
+    }
+
+    fn lobby(&mut self) {
+        while !self.free_seats().is_empty() {
+            match self.inbox.recv() {
+                Ok(incoming) => {
+                    self.handle(incoming, None);
+                }
+                Err(_) => return,
+            }
+        }
+    }
+
+    fn play(&mut self) -> Result<Game, MonopolyError> {
+        let players = self
+            .seats
+            .iter()
+            .zip(&self.config.seats)
+            .enumerate()
+            .map(|(seat, (s, bot))| {
+                let name = s.name.clone().unwrap_or(format!("Bot {}", seat + 1));
+                PlayerConfig::new(&name, bot.unwrap_or(self.config.fallback))
+            })
+            .collect();
+        // Network seats get an agent too, to play for them when they don't answer in time
+        let (mut game, mut agents) =
+            Roster::new(players).game(&self.config.board, self.config.seed)?;
+        game.state.rules.end = self.config.end;
+        game.record_events(true);
+        log::info!("Game started with {} seats", self.seats.len());
+        self.broadcast(&ServerMessage::State(serde_json::to_value(
+            game.view().table_state(),
+        )?));
+
+        let mut seen = 0;
+        while let Some(pending) = game.pending() {
+            let seat = game.seat(pending.player)?;
+            let chosen = if self.seats[seat].remote {
+                self.ask(&game, seat, &pending)?
+            } else {
+                None
+            };
+            let action = match chosen {
+                Some(action) => action,
+                None => agents[seat].decide(&game.view(), &pending),
+            };
+            game.apply_or_default(action)?;
+            self.drain(&game);
+
+            self.broadcast(&ServerMessage::Events(game.events()[seen..].to_vec()));
+            seen = game.events().len();
+            self.broadcast(&ServerMessage::State(serde_json::to_value(
+                game.view().table_state(),
+            )?));
+        }
+
+        self.broadcast(&ServerMessage::GameOver {
+            winner: game.winner(),
+            end_reason: game.end_reason(),
+        });
+        Ok(game)
+    }
+
+    /// Deal with whatever has come in meanwhile, so arrivals and rejoins aren't left
+    /// waiting for the next prompt. Answers by now are to prompts already settled.
+    fn drain(&mut self, game: &Game) {
+        while let Ok(incoming) = self.inbox.try_recv() {
+            if let Some(Handled::Answer(seat, _, answer)) = self.handle(incoming, Some(game)) {
+                log::debug!(
+                    "Dropping a late answer to prompt {} [Seat={}]",
+                    answer.seq,
+                    seat
+                );
+            }
+        }
+    }
+
+    /// Prompt a network seat and wait for a legal answer. `None` once the timeout is
+    /// up, whether the client is slow or gone, and straight away while it's away.
+    fn ask(
+        &mut self,
+        game: &Game,
+        seat: usize,
+        pending: &PendingDecision,
+    ) -> Result<Option<Action>, MonopolyError> {
+        self.drain(game);
+        self.prompts += 1;
+        let seq = self.prompts;
+        let view = game.view();
+        let request = Request::new(&view, pending).with_seq(seq);
+        let prompt = ServerMessage::Prompt(serde_json::to_value(&request)?);
+        let Some(conn) = self.seats[seat].conn else {
+            return Ok(None);
+        };
+        self.send(conn, &prompt);
+        if self.seats[seat].away {
+            return Ok(None);
+        }
+
+        let deadline = Instant::now() + self.config.timeout;
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            let Ok(incoming) = self.inbox.recv_timeout(left) else {
+                log::warn!(
+                    "No answer in time, playing for them until they're back [Player={}]",
+                    pending.player
+                );
+                self.seats[seat].away = true;
+                return Ok(None);
+            };
+            match self.handle(incoming, Some(game)) {
+                Some(Handled::Answer(s, _, answer)) if s == seat && answer.seq != seq => {
+                    log::debug!(
+                        "Dropping a late answer to prompt {} [Seat={}]",
+                        answer.seq,
+                        s
+                    );
+                }
+                Some(Handled::Answer(s, conn, answer)) if s == seat => {
+                    match answer.action.action(&request.legal_actions) {
+                        Some(action) if view.is_legal(&action) => return Ok(Some(action)),
+                        other => self.send(
+                            conn,
+                            &ServerMessage::Rejected(format!("Illegal action {:?}", other)),
+                        ),
+                    }
+                }
+                Some(Handled::Answer(_, conn, _)) => {
+                    self.send(conn, &ServerMessage::Rejected("Not your turn".to_string()))
+                }
+                Some(Handled::Rejoined(s)) if s == seat => {
+                    if let Some(conn) = self.seats[seat].conn {
+                        self.send(conn, &prompt);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn handle(&mut self, incoming: Incoming, game: Option<&Game>) -> Option<Handled> {
+        match incoming {
+            Incoming::Connected(conn, stream) => {
+                // So a client that stops reading can't hold up the game
+                let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+                self.conns.insert(conn, stream);
+            }
+            Incoming::Disconnected(conn) => self.drop_conn(conn),
+            Incoming::Unreadable(conn, line) => self.send(
+                conn,
+                &ServerMessage::Rejected(format!("Couldn't read '{}'", line)),
+            ),
+            Incoming::Message(conn, message) => return self.message(conn, message, game),
+        }
+        None
+    }
+
+    fn message(
+        &mut self,
+        conn: ConnId,
+        message: ClientMessage,
+        game: Option<&Game>,
+    ) -> Option<Handled> {
+        for seat in self.seats.iter_mut().filter(|s| s.conn == Some(conn)) {
+            seat.away = false;
+        }
+        match message {
+            ClientMessage::Join { name, seat } => {
+                let free = self.free_seats();
+                let seat = match seat {
+                    Some(seat) => free.contains(&seat).then_some(seat),
+                    None => free.first().copied(),
+                };
+                match (game, seat) {
+                    (None, Some(seat)) => {
+                        let key = new_key();
+                        self.seats[seat] = Seat {
+                            remote: true,
+                            name: Some(name),
+                            key: Some(key.clone()),
+                            conn: Some(conn),
+                            away: false,
+                        };
+                        self.welcome(conn, seat, key);
+                        self.broadcast(&ServerMessage::Lobby {
+                            free: self.free_seats(),
+                        });
+                    }
+                    _ => self.send(
+                        conn,
+                        &ServerMessage::Rejected("No such seat free, spectate instead".into()),
+                    ),
+                }
+            }
+            ClientMessage::Rejoin { key } => {
+                let seat = self
+                    .seats
+                    .iter()
+                    .position(|s| s.key.as_deref() == Some(key.as_str()));
+                match seat {
+                    Some(seat) => {
+                        self.seats[seat].conn = Some(conn);
+                        self.seats[seat].away = false;
+                        self.welcome(conn, seat, key);
+                        self.catch_up(conn, game);
+                        return Some(Handled::Rejoined(seat));
+                    }
+                    None => self.send(conn, &ServerMessage::Rejected("Unknown key".into())),
+                }
+            }
+            ClientMessage::Spectate => {
+                self.spectators.push(conn);
+                self.send(
+                    conn,
+                    &ServerMessage::Lobby {
+                        free: self.free_seats(),
+                    },
+                );
+                self.catch_up(conn, game);
+            }
+            ClientMessage::Answer(answer) => {
+                match self.seats.iter().position(|s| s.conn == Some(conn)) {
+                    Some(seat) => return Some(Handled::Answer(seat, conn, answer)),
+                    None => self.send(conn, &ServerMessage::Rejected("Not seated".into())),
+                }
+            }
+        }
+        None
+    }
+
+    fn welcome(&mut self, conn: ConnId, seat: usize, key: String) {
+        log::info!("Seat {} taken [Conn={}]", seat, conn);
+        self.send(
+            conn,
+            &ServerMessage::Welcome {
+                seat,
+                player: seat + 1,
+                key,
+            },
+        );
+    }
+
+    /// The table as it stands, for anyone arriving mid-game
+    fn catch_up(&mut self, conn: ConnId, game: Option<&Game>) {
+        if let Some(state) = game.and_then(|g| serde_json::to_value(g.view().table_state()).ok()) {
+            self.send(conn, &ServerMessage::State(state));
+        }
+    }
+
+    fn drop_conn(&mut self, conn: ConnId) {
+        if let Some(stream) = self.conns.remove(&conn) {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+        self.spectators.retain(|&c| c != conn);
+        for seat in self.seats.iter_mut().filter(|s| s.conn == Some(conn)) {
+            log::info!("Seat lost its connection [Conn={}]", conn);
+            seat.conn = None;
+        }
+    }
+
+    fn send(&mut self, conn: ConnId, message: &ServerMessage) {
+        let Some(stream) = self.conns.get_mut(&conn) else {
+            return;
+        };
+        let Ok(mut line) = serde_json::to_string(message) else {
+            return;
+        };
+        line.push('\n');
+        if stream.write_all(line.as_bytes()).is_err() {
+            self.drop_conn(conn);
+        }
+    }
+
+    /// To the seated players and the spectators
+    fn broadcast(&mut self, message: &ServerMessage) {
+        let mut conns: Vec<ConnId> = self.seats.iter().filter_map(|s| s.conn).collect();
+        conns.extend(&self.spectators);
+        for conn in conns {
+            self.send(conn, message);
+        }
+    }
+}
+
+/// Hard enough to guess on a LAN
+fn new_key() -> String {
+    format!("{:016x}", RandomState::new().build_hasher().finish())
+}
+
+/// Talks to a `Server`, e.g. from a bot or a test
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let writer = TcpStream::connect(addr)?;
+        writer.set_nodelay(true)?;
+        Ok(Self {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+        })
+    }
+
+    pub fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.writer.set_read_timeout(timeout)
+    }
+
+    pub fn send(&mut self, message: &ClientMessage) -> Result<(), MonopolyError> {
+        let mut line = serde_json::to_string(message)?;
+        line.push('\n');
+        Ok(self.writer.write_all(line.as_bytes())?)
+    }
+
+    /// `None` once the server has hung up
+    pub fn recv(&mut self) -> Result<Option<ServerMessage>, MonopolyError> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&line)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::{
+        external::{Answer, Reply},
+        strategy::Strategy,
+        termination::EndConditions,
+    };
+
+    use super::{Client, ClientMessage, Server, ServerConfig, ServerMessage};
+
+    fn server(seats: Vec<Option<Strategy>>) -> (Server, String) {
+        let config = ServerConfig::new(seats)
+            .with_seed(3)
+            .with_end(EndConditions::last_standing().with_max_rounds(5))
+            .with_timeout(Duration::from_secs(10));
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+        (server, addr)
+    }
+
+    fn client(addr: &str) -> Client {
+        let client = Client::connect(addr).unwrap();
+        client.set_timeout(Some(Duration::from_secs(20))).unwrap();
+        client
+    }
+
+    fn answer(prompt: &ServerMessage, index: usize) -> ClientMessage {
+        let ServerMessage::Prompt(request) = prompt else {
+            panic!("Not a prompt: {:?}", prompt);
+        };
+        ClientMessage::Answer(Answer {
+            seq: request["seq"].as_u64().unwrap(),
+            action: Reply::Index(index),
+        })
+    }
+
+    fn next_prompt(client: &mut Client) -> ServerMessage {
+        loop {
+            let message = client.recv().unwrap().unwrap();
+            if matches!(message, ServerMessage::Prompt(_)) {
+                return message;
+            }
+        }
+    }
+
+    /// Answers every prompt with the do-nothing choice, returning what it was sent
+    fn play_out(mut client: Client) -> Vec<ServerMessage> {
+        let mut received = vec![];
+        while let Some(message) = client.recv().unwrap() {
+            if let ServerMessage::Prompt(_) = message {
+                client.send(&answer(&message, 0)).unwrap();
+            }
+            let over = matches!(message, ServerMessage::GameOver { .. });
+            received.push(message);
+            if over {
+                break;
+            }
+        }
+        received
+    }
+
+    #[test]
+    fn players_and_spectators_share_one_game() {
+        let (server, addr) = server(vec![None, None, Some(Strategy::Greedy)]);
+
+        let watching = thread::spawn(move || {
+            let mut spectator = client(&addr);
+            spectator.send(&ClientMessage::Spectate).unwrap();
+            assert!(matches!(
+                spectator.recv().unwrap(),
+                Some(ServerMessage::Lobby { free }) if free == [0, 1]
+            ));
+
+            let players: Vec<_> = ["Ada", "Bob"]
+                .iter()
+                .map(|name| {
+                    let mut player = client(&addr);
+                    player
+                        .send(&ClientMessage::Join {
+                            name: name.to_string(),
+                            seat: None,
+                        })
+                        .unwrap();
+                    thread::spawn(move || play_out(player))
+                })
+                .collect();
+            let watched = play_out(spectator);
+            let played: Vec<_> = players.into_iter().map(|p| p.join().unwrap()).collect();
+            (watched, played)
+        });
+        let game = server.run().unwrap();
+        let (watched, played) = watching.join().unwrap();
+
+        for received in played {
+            assert!(matches!(received[0], ServerMessage::Welcome { .. }));
+            assert!(received
+                .iter()
+                .any(|m| matches!(m, ServerMessage::Prompt(_))));
+            assert!(matches!(
+                received.last(),
+                Some(ServerMessage::GameOver { .. })
+            ));
+        }
+        let events: usize = watched
+            .iter()
+            .map(|m| match m {
+                ServerMessage::Events(events) => events.len(),
+                _ => 0,
+            })
+            .sum();
+        assert_eq!(events, game.events().len());
+        assert!(game.is_over());
+        assert_eq!(game.player(3).unwrap().name, "Bot 3");
+    }
+
+    #[test]
+    fn dropped_players_rejoin_their_seat() {
+        let (server, addr) = server(vec![None, Some(Strategy::Greedy)]);
+
+        let playing = thread::spawn(move || {
+            let mut first = client(&addr);
+            first
+                .send(&ClientMessage::Join {
+                    name: "Ada".to_string(),
+                    seat: Some(0),
+                })
+                .unwrap();
+            let Some(ServerMessage::Welcome { key, seat: 0, .. }) = first.recv().unwrap() else {
+                panic!("Not welcomed");
+            };
+            // Hang up on the first prompt without answering it
+            while !matches!(first.recv().unwrap(), Some(ServerMessage::Prompt(_))) {}
+            drop(first);
+
+            let mut second = client(&addr);
+            second.send(&ClientMessage::Rejoin { key }).unwrap();
+            assert!(matches!(
+                second.recv().unwrap(),
+                Some(ServerMessage::Welcome { seat: 0, .. })
+            ));
+            let prompt = next_prompt(&mut second);
+            // An answer to no prompt in particular is dropped unseen
+            second
+                .send(&ClientMessage::Answer(Answer {
+                    seq: 0,
+                    action: Reply::Index(99),
+                }))
+                .unwrap();
+            second.send(&answer(&prompt, 99)).unwrap();
+            assert!(matches!(
+                second.recv().unwrap(),
+                Some(ServerMessage::Rejected(_))
+            ));
+            second.send(&answer(&prompt, 0)).unwrap();
+            play_out(second)
+        });
+        let game = server.run().unwrap();
+
+        let received = playing.join().unwrap();
+        assert!(matches!(
+            received.last(),
+            Some(ServerMessage::GameOver { .. })
+        ));
+        assert_eq!(game.player(1).unwrap().name, "Ada");
+    }
+
+    #[test]
+    fn empty_seats_are_played_without_waiting() {
+        let config = ServerConfig::new(vec![None, None])
+            .with_seed(3)
+            .with_end(EndConditions::last_standing().with_max_rounds(5))
+            .with_timeout(Duration::from_secs(60));
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+
+        let playing = thread::spawn(move || {
+            let mut gone = client(&addr);
+            gone.send(&ClientMessage::Join {
+                name: "Ada".to_string(),
+                seat: Some(0),
+            })
+            .unwrap();
+            assert!(matches!(
+                gone.recv().unwrap(),
+                Some(ServerMessage::Welcome { seat: 0, .. })
+            ));
+            drop(gone);
+            // Leave the lobby time to see Ada go before the game starts
+            thread::sleep(Duration::from_millis(200));
+
+            let mut staying = client(&addr);
+            staying
+                .send(&ClientMessage::Join {
+                    name: "Bob".to_string(),
+                    seat: Some(1),
+                })
+                .unwrap();
+            play_out(staying)
+        });
+        let start = Instant::now();
+        let game = server.run().unwrap();
+
+        assert!(matches!(
+            playing.join().unwrap().last(),
+            Some(ServerMessage::GameOver { .. })
+        ));
+        assert!(game.is_over());
+        assert!(start.elapsed() < Duration::from_secs(30));
+    }
+
+    #[test]
+    fn seats_that_miss_a_prompt_are_not_waited_for() {
+        let config = ServerConfig::new(vec![None, Some(Strategy::Greedy)])
+            .with_seed(3)
+            .with_end(EndConditions::last_standing().with_max_rounds(30))
+            .with_timeout(Duration::from_secs(1));
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+        let addr = server.local_addr().unwrap().to_string();
+
+        let idle = thread::spawn(move || {
+            let mut idle = client(&addr);
+            idle.send(&ClientMessage::Join {
+                name: "Ada".to_string(),
+                seat: Some(0),
+            })
+            .unwrap();
+            let mut prompts = 0;
+            while let Some(message) = idle.recv().unwrap() {
+                match message {
+                    ServerMessage::Prompt(_) => prompts += 1,
+                    ServerMessage::GameOver { .. } => break,
+                    _ => {}
+                }
+            }
+            prompts
+        });
+        let start = Instant::now();
+        let game = server.run().unwrap();
+
+        // Waiting out every prompt would take at least a second each
+        let prompts = idle.join().unwrap();
+        assert!(prompts > 10);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(game.is_over());
+    }
+}
@@ -1,18 +1,84 @@
-use rand::distributions::{Distribution, Uniform};
 use std::{cell::RefCell, collections::HashMap};
 
-use rand::rngs::ThreadRng;
-
 use crate::config::Property;
-use crate::locations::BoardLocation;
+use crate::error::MonopolyError;
+use crate::locations::{BoardLocation, ColourGroup, SquareKind};
 use crate::payment::Payment;
 use crate::player::{Player, PlayerId};
 
 pub type BoardPosition = usize;
-pub type MoveTo = usize;
+/// Where a card or square sends the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveTo {
+    Go,
+    Jail,
+    /// The square at this index of the board's `chance_destinations`
+    ChanceDestination(usize),
+}
 pub type FreeParking = usize;
 
+/// What landing on a square asks of the player. Nothing is paid here, the
+/// engine settles it so that every payment can go through the same checks.
+#[derive(Debug, Default)]
+pub struct Landing {
+    pub move_to: Option<MoveTo>,
+    pub rent: Option<Payment>,
+    /// Paid by the bank
+    pub collect: usize,
+    /// Fines and taxes, paid into the free parking pot
+    pub fine: FreeParking,
+    /// Pay this much per house and per hotel owned, into the free parking pot
+    pub repairs: Option<(usize, usize)>,
+}
+
+impl Landing {
+    const fn move_to(position: MoveTo) -> Self {
+        Self {
+            move_to: Some(position),
+            rent: None,
+            collect: 0,
+            fine: 0,
+            repairs: None,
+        }
+    }
+
+    const fn collect(amount: usize) -> Self {
+        Self {
+            move_to: None,
+            rent: None,
+            collect: amount,
+            fine: 0,
+            repairs: None,
+        }
+    }
+
+    const fn fine(amount: usize) -> Self {
+        Self {
+            move_to: None,
+            rent: None,
+            collect: 0,
+            fine: amount,
+            repairs: None,
+        }
+    }
+
+    const fn repairs(per_house: usize, per_hotel: usize) -> Self {
+        Self {
+            move_to: None,
+            rent: None,
+            collect: 0,
+            fine: 0,
+            repairs: Some((per_house, per_hotel)),
+        }
+    }
+}
+
+/// Cards are numbered from 1, see `chance_space` and `community_chest_space`
+pub const CHANCE_CARDS: usize = 16;
+pub const COMMUNITY_CHEST_CARDS: usize = 14;
+
 #[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PropertyState {
     pub ownable: bool,
     pub owner: Option<PlayerId>,
@@ -23,64 +89,107 @@ pub struct PropertyState {
 
 impl PropertyState {
     #[allow(dead_code)]
-    pub const fn new(square: BoardLocation) -> Self {
-        match square {
-            BoardLocation::Go
-            | BoardLocation::IncomeTax
-            | BoardLocation::Chance1
-            | BoardLocation::Chance2
-            | BoardLocation::Chance3
-            | BoardLocation::CommunityChest1
-            | BoardLocation::CommunityChest2
-            | BoardLocation::Jail
-            | BoardLocation::FreeParking
-            | BoardLocation::GoToJail => Self {
-                ownable: false,
-                owner: None,
-                house_count: 0,
-                hotel_count: 0,
-                mortgaged: false,
-            },
-            _ => Self {
-                ownable: true,
-                owner: None,
-                house_count: 0,
-                hotel_count: 0,
-                mortgaged: false,
-            },
+    pub const fn new(kind: SquareKind) -> Self {
+        Self {
+            ownable: kind.is_ownable(),
+            owner: None,
+            house_count: 0,
+            hotel_count: 0,
+            mortgaged: false,
         }
     }
+
+    /// Houses and hotels, the hotel being recorded as a fifth house
+    pub fn buildings(&self) -> (usize, usize) {
+        let houses = self.house_count.min(4);
+        (houses, self.house_count - houses + self.hotel_count)
+    }
 }
 
 #[allow(dead_code)]
+#[derive(Debug, Clone)]
 pub struct BoardSquare {
     position_id: BoardPosition,
-    square: BoardLocation,
+    name: String,
+    kind: SquareKind,
+    group: Option<ColourGroup>,
     cost: usize,
-    charge: usize,
+    rent: [usize; 6],
+    mortgage: usize,
     house_cost: usize,
     hotel_cost: usize,
+    tax: usize,
+    tax_rate: usize,
     state: RefCell<PropertyState>,
 }
 
 impl BoardSquare {
+    /// A square of the UK board, described by its entry in `location_config`
     pub fn new(
         location: BoardLocation,
         location_config: &HashMap<BoardLocation, Property>,
-    ) -> Self {
-        let details = location_config.get(&location).unwrap();
+    ) -> Result<Self, MonopolyError> {
+        let details = location_config
+            .get(&location)
+            .ok_or(MonopolyError::MissingSquare(location))?;
+
+        Ok(Self::from_property(
+            location.position(),
+            &Property {
+                name: location.name().to_string(),
+                kind: location.kind(),
+                group: location.colour_group(),
+                ..details.clone()
+            },
+        ))
+    }
 
+    pub fn from_property(position: BoardPosition, details: &Property) -> Self {
         Self {
-            position_id: 1,
-            state: RefCell::new(PropertyState::new(location)),
+            position_id: position,
+            state: RefCell::new(PropertyState::new(details.kind)),
+            name: details.name.clone(),
+            kind: details.kind,
+            group: details.group,
             cost: details.tile_cost,
-            square: location,
-            charge: details.charge,
+            rent: details.rent,
+            mortgage: details.mortgage,
             house_cost: details.house_cost,
             hotel_cost: details.hotel_cost,
+            tax: details.tax,
+            tax_rate: details.tax_rate,
         }
     }
 
+    pub const fn position(&self) -> BoardPosition {
+        self.position_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub const fn kind(&self) -> SquareKind {
+        self.kind
+    }
+
+    pub const fn colour_group(&self) -> Option<ColourGroup> {
+        self.group
+    }
+
+    /// Flat tax and the percentage of net worth that may be paid instead
+    pub const fn tax(&self) -> (usize, usize) {
+        (self.tax, self.tax_rate)
+    }
+
+    pub fn property_state(&self) -> PropertyState {
+        self.state.borrow().clone()
+    }
+
+    pub(crate) fn set_property_state(&self, state: PropertyState) {
+        *self.state.borrow_mut() = state;
+    }
+
     pub fn is_ownable(&self) -> bool {
         let s = self.state.borrow();
         s.ownable
@@ -103,26 +212,100 @@ impl BoardSquare {
         }
     }
 
+    pub fn is_mortgaged(&self) -> bool {
+        self.state.borrow().mortgaged
+    }
+
+    pub fn house_count(&self) -> usize {
+        self.state.borrow().house_count
+    }
+
     pub const fn get_purchase_cost(&self) -> usize {
         self.cost
     }
 
-    pub fn rent_cost(&self) -> usize {
-        let s = self.state.borrow();
-        println!("{}-{}", s.house_count, s.hotel_count);
-        if s.hotel_count + s.house_count == 0 {
-            self.charge
-        } else if s.hotel_count < 1 {
-            s.house_count * self.charge + self.charge
-        } else {
-            s.hotel_count * 5 * self.charge + self.charge
+    pub const fn mortgage_value(&self) -> usize {
+        self.mortgage
+    }
+
+    /// Lifting a mortgage costs the mortgage value plus 10% interest
+    pub const fn unmortgage_cost(&self) -> usize {
+        self.mortgage_value() + self.mortgage_value() / 10
+    }
+
+    /// Houses and hotels standing on the square, a fifth house being the hotel
+    pub fn buildings(&self) -> (usize, usize) {
+        self.state.borrow().buildings()
+    }
+
+    /// Price of a house and of the hotel
+    pub const fn building_costs(&self) -> (usize, usize) {
+        (self.house_cost, self.hotel_cost)
+    }
+
+    /// What the buildings on the square cost to put up
+    pub fn building_value(&self) -> usize {
+        let (houses, hotels) = self.buildings();
+        houses * self.house_cost + hotels * self.hotel_cost
+    }
+
+    /// What selling every building back to the bank would raise
+    pub fn building_sale_value(&self) -> usize {
+        let (houses, hotels) = self.buildings();
+        houses * (self.house_cost / 2) + hotels * (self.hotel_cost / 2)
+    }
+
+    /// Looked up from the rent table by the buildings standing on the square, doubled
+    /// when unimproved in a complete set. Stations go by how many of the group the owner
+    /// holds, this one included, and utilities charge a multiple of the dice thrown.
+    pub fn rent_cost(&self, owned_in_group: usize, full_set: bool, dice_total: usize) -> usize {
+        let (houses, hotels) = self.buildings();
+        let owned = owned_in_group.clamp(1, self.rent.len()) - 1;
+        match self.kind {
+            SquareKind::Station => self.rent[owned],
+            SquareKind::Utility => self.rent[owned] * dice_total,
+            _ if hotels > 0 => self.rent[5],
+            _ if houses == 0 && full_set => self.rent[0] * 2,
+            _ => self.rent[houses],
+        }
+    }
+
+    pub fn purchase_property(&self, player: &Player) -> Result<(), MonopolyError> {
+        self.auction_property(player, self.cost)
+    }
+
+    /// Sold at auction, so the winning bid is paid rather than the listed price
+    pub fn auction_property(&self, player: &Player, price: usize) -> Result<(), MonopolyError> {
+        player.pay(price)?;
+        self.state.borrow_mut().owner = Some(player.id);
+        Ok(())
+    }
+
+    /// Hand the square to a new owner (or back to the bank), keeping any mortgage
+    pub fn transfer(&self, owner: Option<PlayerId>) {
+        let mut s = self.state.borrow_mut();
+        s.owner = owner;
+        if owner.is_none() {
+            s.mortgaged = false;
+            s.house_count = 0;
+            s.hotel_count = 0;
         }
     }
 
-    pub fn purchase_property(&self, player: &Player) {
+    pub fn mortgage(&self, player: &Player) -> Result<(), MonopolyError> {
         let mut s = self.state.borrow_mut();
-        s.owner = Some(player.id);
-        player.pay(self.cost);
+        if s.owner != Some(player.id) || s.mortgaged || s.house_count > 0 || s.hotel_count > 0 {
+            return Err(MonopolyError::CannotMortgage(self.position_id));
+        }
+        s.mortgaged = true;
+        player.deposit(self.mortgage_value());
+        Ok(())
+    }
+
+    pub fn unmortgage(&self, player: &Player) -> Result<(), MonopolyError> {
+        player.pay(self.unmortgage_cost())?;
+        self.state.borrow_mut().mortgaged = false;
+        Ok(())
     }
 
     pub fn upgradable(&self) -> bool {
@@ -141,61 +324,64 @@ impl BoardSquare {
         }
     }
 
-    fn upgrade(&self, player: &Player) {
+    fn upgrade(&self) {
         let mut s = self.state.borrow_mut();
         if s.house_count <= 4 {
             s.house_count += 1;
-            player.add_house();
         } else if s.hotel_count == 0 {
             s.hotel_count += 1;
-            player.add_hotel();
         }
     }
 
-    #[allow(dead_code)]
-    pub fn purchase_upgrade(&self, player: &Player) {
-        player.pay(self.upgrade_cost());
-
-        if player.is_active() {
-            self.upgrade(player);
+    pub fn purchase_upgrade(&self, player: &Player) -> Result<(), MonopolyError> {
+        if !self.upgradable() || self.upgrade_cost() == 0 {
+            return Err(MonopolyError::CannotBuild(self.position_id));
         }
+        player.pay(self.upgrade_cost())?;
+        self.upgrade();
+        Ok(())
+    }
+
+    /// Sell the most recent building back to the bank at half of what it cost
+    pub fn sell_upgrade(&self, player: &Player) -> usize {
+        let mut s = self.state.borrow_mut();
+        let refund = match s.house_count {
+            0 => return 0,
+            5 => self.hotel_cost / 2,
+            _ => self.house_cost / 2,
+        };
+        s.house_count -= 1;
+        player.deposit(refund);
+        refund
     }
 
     /// Community Chest
-    fn community_chest_space(
-        &self,
-        player: &Player,
-        rng: &mut ThreadRng,
-    ) -> (Option<MoveTo>, Option<Payment>, FreeParking) {
-        let distribution = Uniform::new_inclusive(1, 14);
-        match distribution.sample(rng) {
+    fn community_chest_space(&self, player: &Player, card: usize) -> Landing {
+        match card {
             1 => {
                 log::info!("Community Chest: Advance to Go [Player={}]", player.id);
-                (Some(0), None, 0)
+                Landing::move_to(MoveTo::Go)
             }
             2 => {
                 log::info!(
                     "Community Chest: Banking error collect 200 [Player={}]",
                     player.id
                 );
-                player.deposit(200);
-                (None, None, 0)
+                Landing::collect(200)
             }
             3 => {
                 log::info!(
                     "Community Chest: Doctors Fees pay 50 [Player={}]",
                     player.id
                 );
-                player.pay(50);
-                (None, None, 50)
+                Landing::fine(50)
             }
             4 => {
                 log::info!(
                     "Community Chest: Sale from Stock collect 50 [Player={}]",
                     player.id
                 );
-                player.deposit(50);
-                (None, None, 0)
+                Landing::collect(50)
             }
             5 => {
                 log::info!(
@@ -203,23 +389,21 @@ impl BoardSquare {
                     player.id
                 );
                 player.jail_card();
-                (None, None, 0)
+                Landing::default()
             }
             6 => {
                 log::info!(
                     "Community Chest: Holiday Xmas func matures collect 100 [Player={}]",
                     player.id
                 );
-                player.deposit(100);
-                (None, None, 0)
+                Landing::collect(100)
             }
             7 => {
                 log::info!(
                     "Community Chest: Income Tax refund collect 20 [Player={}]",
                     player.id
                 );
-                player.deposit(20);
-                (None, None, 0)
+                Landing::collect(20)
             }
             // 8
             // TODO Its your birthday collect 10 from each player
@@ -228,216 +412,151 @@ impl BoardSquare {
                     "Community Chest: Life Insurance matures collect 100 [Player={}]",
                     player.id
                 );
-                player.deposit(100);
-                (None, None, 0)
+                Landing::collect(100)
             }
             10 => {
                 log::info!(
                     "Community Chest: Hospital fees pay 50 [Player={}]",
                     player.id
                 );
-                player.pay(50);
-                (None, None, 50)
+                Landing::fine(50)
             }
             11 => {
                 log::info!(
                     "Community Chest: Street repairs 40 per house and 115 per hotel [Player={}]",
                     player.id
                 );
-                let (houses, hotels) = player.count_properties();
-                let amount = 40 * houses + 115 * hotels;
-                player.pay(amount);
-                (None, None, amount)
+                Landing::repairs(40, 115)
             }
             12 => {
                 log::info!(
                     "Community Chest: Second play in a Beauty competition collect 20 [Player={}]",
                     player.id
                 );
-                player.deposit(20);
-                (None, None, 0)
+                Landing::collect(20)
             }
             _ => {
                 log::info!(
                     "Community Chest: Your inheritance comes through collect 100 [Player={}]",
                     player.id
                 );
-                player.deposit(100);
-                (None, None, 0)
+                Landing::collect(100)
             }
         }
     }
 
     // Chance
-    fn chance_space(
-        &self,
-        player: &Player,
-        rng: &mut ThreadRng,
-    ) -> (Option<MoveTo>, Option<Payment>, FreeParking) {
-        let distribution = Uniform::new_inclusive(1, 16);
-        match distribution.sample(rng) {
+    fn chance_space(&self, player: &Player, card: usize) -> Landing {
+        match card {
             1 => {
                 log::info!("Chance: Advance to Go [Player={}]", player.id);
-                (Some(0), None, 0)
+                Landing::move_to(MoveTo::Go)
             }
             2 => {
                 log::info!("Chance: Go to jail [Player={}]", player.id);
                 player.go_to_jail();
-                (Some(9), None, 0)
+                Landing::move_to(MoveTo::Jail)
             }
-            3 => {
+            // Where to is up to the board, the engine logs the square
+            3..=5 => {
                 log::info!(
-                    "Chance: Advance to Pall Mall. If you pass Go collection 200 [Player={}]",
+                    "Chance: Advance, if you pass Go collect 200 [Player={}]",
                     player.id
                 );
-                (Some(10), None, 0)
-            }
-            4 => {
-                log::info!("Chance: Take a trip to Marylebone Station and if you pass Go collect 200 [Player={}]", player.id);
-                (Some(4), None, 0)
-            }
-            5 => {
-                log::info!(
-                    "Chance: Advance to Trafalgar Square. If you pass Go collect 200 [Player={}]",
-                    player.id
-                );
-                (Some(23), None, 0)
+                Landing::move_to(MoveTo::ChanceDestination(card - 3))
             }
             6 => {
-                log::info!("Chance: Advance to Mayfair [Player={}]", player.id);
-                (Some(38), None, 0)
+                log::info!("Chance: Advance [Player={}]", player.id);
+                Landing::move_to(MoveTo::ChanceDestination(3))
             }
             7 => {
                 log::info!("Chance: Make general repairs on all of your houses. For each house pay \u{a3}25. For each hotel pay \u{a3}100 [Player={}]", player.id);
-                let (houses, hotels) = player.count_properties();
-                let amount = 25 * houses + 100 * hotels;
-                player.pay(amount);
-                (None, None, amount)
+                Landing::repairs(25, 100)
             }
             8 => {
                 log::info!(
                     "Chance: Street repairs 40 per house and 115 per hotel [Player={}]",
                     player.id
                 );
-                let (houses, hotels) = player.count_properties();
-                let amount = 40 * houses + 115 * hotels;
-                player.pay(amount);
-                (None, None, amount)
+                Landing::repairs(40, 115)
             }
             9 => {
                 log::info!("Chance: Pay school fees of 150 [Player={}]", player.id);
-                player.pay(150);
-                (None, None, 150)
+                Landing::fine(150)
             }
             10 => {
                 log::info!("Chance: Drunk in charge fine 20 [Player={}]", player.id);
-                player.pay(20);
-                (None, None, 20)
+                Landing::fine(20)
             }
             11 => {
                 log::info!("Chance: Speeding fine pay 16 [Player={}]", player.id);
-                player.pay(15);
-                (None, None, 15)
+                Landing::fine(15)
             }
             13 => {
                 log::info!(
                     "Chance: Your building loan matures. Receive 150 [Player={}]",
                     player.id
                 );
-                player.deposit(150);
-                (None, None, 0)
+                Landing::collect(150)
             }
             14 => {
                 log::info!(
                     "Chance: You have won a crossword competition. Collect 100 [Player={}]",
                     player.id
                 );
-                player.deposit(100);
-                (None, None, 0)
+                Landing::collect(100)
             }
             15 => {
                 log::info!("Chance: Get out of jail free card [Player={}]", player.id);
                 player.jail_card();
-                (None, None, 0)
+                Landing::default()
             }
             _ => {
                 log::info!(
                     "Chance: Bank pays you dividend of 50 [Player={}]",
                     player.id
                 );
-                player.deposit(50);
-                (Some(0), None, 0)
+                Landing::collect(50)
             }
         }
     }
 
+    /// `card` is the one drawn if the square is Chance or Community Chest and `rent` is
+    /// what its owner charges, see `Game::rent`
     #[allow(dead_code)]
     pub fn take_step(
         &self,
         new_position: &Self,
         player: &Player,
-        rng: &mut ThreadRng,
-    ) -> (Option<MoveTo>, Option<Payment>, FreeParking) {
-        match new_position.square {
-            BoardLocation::Go => {
-                player.deposit(200);
-                (None, None, 0)
-            }
-            BoardLocation::IncomeTax => {
-                player.pay(200); // OR 10% of the value of the Assets
-                (None, None, 0)
-            }
-            BoardLocation::LuxuryTax => {
-                player.pay(75);
-                (None, None, 0)
-            }
-            BoardLocation::Chance1 | BoardLocation::Chance2 | BoardLocation::Chance3 => {
-                self.chance_space(player, rng)
-            }
-            BoardLocation::CommunityChest1 | BoardLocation::CommunityChest2 => {
-                self.community_chest_space(player, rng)
-            }
-            BoardLocation::GoToJail => {
+        card: usize,
+        rent: usize,
+    ) -> Landing {
+        match new_position.kind {
+            SquareKind::Go => Landing::collect(200),
+            // The engine offers the choice when the square also has a `tax_rate`
+            SquareKind::Tax => Landing::fine(new_position.tax),
+            SquareKind::Chance => self.chance_space(player, card),
+            SquareKind::CommunityChest => self.community_chest_space(player, card),
+            SquareKind::GoToJail => {
                 player.go_to_jail();
-                (Some(9), None, 0)
-            }
-            BoardLocation::Jail => (None, None, 0), // Just Visiting
-            _ => {
-                // Not bought
-                if self.is_ownable() && player.can_afford(self.get_purchase_cost()) {
-                    self.purchase_property(player);
-                    return (None, None, 0);
+                Landing::move_to(MoveTo::Jail)
+            }
+            SquareKind::Jail | SquareKind::FreeParking => Landing::default(), // Just Visiting
+            _ => match new_position.owner_id() {
+                // Not bought - the engine offers it for sale
+                None => Landing::default(),
+                Some(owner) if owner == player.id || new_position.is_mortgaged() => {
+                    Landing::default()
                 }
-
-                // Owned by Player - maybe an upgrade?
-                let rent = self.rent_cost();
-                if self.is_owned_by_player(player) && player.can_afford(self.upgrade_cost()) {
-                    self.purchase_upgrade(player);
-                    (None, None, 0)
-                } else if player.can_afford(rent) {
-                    player.pay(rent);
-
-                    (
-                        None,
-                        Some(Payment {
-                            to: self.owner_id().unwrap(),
-                            amount: rent,
-                            terminal: false,
-                        }),
-                        0,
-                    )
-                } else {
-                    (
-                        None,
-                        Some(Payment {
-                            to: self.owner_id().unwrap(),
-                            amount: rent,
-                            terminal: true,
-                        }),
-                        0,
-                    )
-                }
-            }
+                Some(owner) => Landing {
+                    rent: Some(Payment {
+                        to: owner,
+                        amount: rent,
+                        terminal: !player.can_afford(rent),
+                    }),
+                    ..Landing::default()
+                },
+            },
         }
     }
 }
@@ -446,7 +565,11 @@ impl BoardSquare {
 mod test {
     use std::collections::HashMap;
 
-    use crate::{config::Property, player::Player};
+    use crate::{
+        config::{default_config, Property},
+        error::MonopolyError,
+        player::Player,
+    };
 
     use super::{BoardLocation, BoardSquare};
 
@@ -455,10 +578,16 @@ mod test {
         let mut config = HashMap::new();
         config.insert(
             BoardLocation::Piccadilly,
-            Property::new(BoardLocation::VineStreet, 300, 50, 100, 200),
+            Property::new(
+                BoardLocation::VineStreet,
+                300,
+                [50, 200, 600, 1400, 1700, 2000],
+                100,
+                200,
+            ),
         );
 
-        let sq = BoardSquare::new(BoardLocation::Piccadilly, &config);
+        let sq = BoardSquare::new(BoardLocation::Piccadilly, &config).unwrap();
         assert!(sq.is_ownable());
     }
 
@@ -467,10 +596,10 @@ mod test {
         let mut config = HashMap::new();
         config.insert(
             BoardLocation::Go,
-            Property::new(BoardLocation::VineStreet, 0, 0, 0, 0),
+            Property::new(BoardLocation::VineStreet, 0, [0; 6], 0, 0),
         );
 
-        let sq = BoardSquare::new(BoardLocation::Go, &config);
+        let sq = BoardSquare::new(BoardLocation::Go, &config).unwrap();
         assert!(!sq.is_ownable());
     }
 
@@ -479,41 +608,96 @@ mod test {
         let mut config = HashMap::new();
         config.insert(
             BoardLocation::Piccadilly,
-            Property::new(BoardLocation::VineStreet, 300, 50, 100, 200),
+            Property::new(
+                BoardLocation::VineStreet,
+                300,
+                [50, 200, 600, 1400, 1700, 2000],
+                100,
+                200,
+            ),
         );
 
-        let sq = BoardSquare::new(BoardLocation::Piccadilly, &config);
+        let sq = BoardSquare::new(BoardLocation::Piccadilly, &config).unwrap();
         let player_one = Player::new(1);
 
-        sq.purchase_property(&player_one);
+        sq.purchase_property(&player_one).unwrap();
         assert!(sq.is_owned());
 
         // No Houses & no hotels
-        assert_eq!(sq.rent_cost(), 50);
+        assert_eq!(sq.rent_cost(1, false, 0), 50);
 
         // 1 Houses & no hotels
         assert_eq!(sq.upgrade_cost(), 100);
-        sq.purchase_upgrade(&player_one);
-        assert_eq!(sq.rent_cost(), 100);
+        sq.purchase_upgrade(&player_one).unwrap();
+        assert_eq!(sq.rent_cost(1, false, 0), 200);
 
         // 2 Houses & no hotels
         assert_eq!(sq.upgrade_cost(), 100);
-        sq.purchase_upgrade(&player_one);
-        assert_eq!(sq.rent_cost(), 150);
+        sq.purchase_upgrade(&player_one).unwrap();
+        assert_eq!(sq.rent_cost(1, false, 0), 600);
 
         // 3 Houses & no hotels
         assert_eq!(sq.upgrade_cost(), 100);
-        sq.purchase_upgrade(&player_one);
-        assert_eq!(sq.rent_cost(), 200);
+        sq.purchase_upgrade(&player_one).unwrap();
+        assert_eq!(sq.rent_cost(1, false, 0), 1400);
 
         // 4 Houses & no hotels
         assert_eq!(sq.upgrade_cost(), 100);
-        sq.purchase_upgrade(&player_one);
-        assert_eq!(sq.rent_cost(), 250);
+        sq.purchase_upgrade(&player_one).unwrap();
+        assert_eq!(sq.rent_cost(1, false, 0), 1700);
 
         // 4 Houses & 1 hotels
         assert_eq!(sq.upgrade_cost(), 200);
-        sq.purchase_upgrade(&player_one);
-        assert_eq!(sq.rent_cost(), 300);
+        sq.purchase_upgrade(&player_one).unwrap();
+        assert_eq!(sq.rent_cost(1, false, 0), 2000);
+        assert_eq!(sq.mortgage_value(), 150);
+    }
+
+    #[test]
+    fn only_the_owner_mortgages_an_empty_square() {
+        let mut config = HashMap::new();
+        config.insert(
+            BoardLocation::Piccadilly,
+            Property::new(BoardLocation::VineStreet, 300, [50; 6], 100, 200),
+        );
+        let sq = BoardSquare::new(BoardLocation::Piccadilly, &config).unwrap();
+        let (owner, other) = (Player::new(1), Player::new(2));
+
+        assert!(matches!(
+            sq.mortgage(&owner),
+            Err(MonopolyError::CannotMortgage(_))
+        ));
+        sq.purchase_property(&owner).unwrap();
+        sq.purchase_upgrade(&owner).unwrap();
+        assert!(sq.mortgage(&owner).is_err());
+        sq.sell_upgrade(&owner);
+        assert!(sq.mortgage(&other).is_err());
+
+        let balance = owner.current_balance();
+        sq.mortgage(&owner).unwrap();
+        assert_eq!(owner.current_balance(), balance + 150);
+        assert!(sq.mortgage(&owner).is_err());
+    }
+
+    #[test]
+    fn rent_depends_on_the_group_and_the_dice() {
+        let config = default_config();
+        let square = |location| BoardSquare::new(location, &config).unwrap();
+
+        let station = square(BoardLocation::KingCrossStation);
+        let rents: Vec<_> = (1..=4).map(|n| station.rent_cost(n, n == 4, 8)).collect();
+        assert_eq!(rents, [25, 50, 100, 200]);
+
+        let utility = square(BoardLocation::WaterCompany);
+        assert_eq!(utility.rent_cost(1, false, 8), 32);
+        assert_eq!(utility.rent_cost(2, true, 8), 80);
+
+        let street = square(BoardLocation::Mayfair);
+        let owner = Player::new(1);
+        street.purchase_property(&owner).unwrap();
+        assert_eq!(street.rent_cost(1, false, 8), 50);
+        assert_eq!(street.rent_cost(2, true, 8), 100);
+        street.purchase_upgrade(&owner).unwrap();
+        assert_eq!(street.rent_cost(2, true, 8), 200);
     }
 }
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{event::Event, game::Game, locations::SquareKind, player::PlayerId};
+
+/// Totals for one square over any number of games
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SquareStats {
+    /// Turns that ended a move on the square, being sent to jail included
+    pub landings: usize,
+    pub rent: usize,
+    /// Paid for the square and the buildings put up on it
+    pub invested: usize,
+}
+
+impl SquareStats {
+    /// Rent collected per unit invested
+    pub fn roi(&self) -> f64 {
+        if self.invested == 0 {
+            0.0
+        } else {
+            self.rent as f64 / self.invested as f64
+        }
+    }
+}
+
+/// What to read off `BoardStats`, e.g. for a heatmap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Share of all landings
+    Landings,
+    /// Mean rent collected per game
+    Rent,
+    Roi,
+}
+
+impl Metric {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "landings" => Some(Self::Landings),
+            "rent" => Some(Self::Rent),
+            "roi" => Some(Self::Roi),
+            _ => None,
+        }
+    }
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Landings => "Landing frequency",
+            Self::Rent => "Rent collected per game",
+            Self::Roi => "Return on investment",
+        }
+    }
+
+    /// How a value is printed on its square
+    pub fn format(self, value: f64) -> String {
+        match self {
+            Self::Landings => format!("{:.1}%", value * 100.0),
+            Self::Rent => format!("{:.0}", value),
+            Self::Roi => format!("{:.2}", value),
+        }
+    }
+}
+
+/// Where one game stands, for adding up its events a few at a time
+#[derive(Debug, Clone)]
+pub struct GameTally {
+    jail: usize,
+    positions: HashMap<PlayerId, usize>,
+    /// Buildings put up in this game so far, a fifth being the hotel
+    houses: Vec<usize>,
+}
+
+/// Per-square statistics gathered from the event logs of finished games
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BoardStats {
+    pub games: usize,
+    pub squares: Vec<SquareStats>,
+}
+
+impl BoardStats {
+    pub fn new(board_size: usize) -> Self {
+        Self {
+            games: 0,
+            squares: vec![SquareStats::default(); board_size],
+        }
+    }
+
+    /// Add up a game played with `Game::record_events` on
+    pub fn add_game(&mut self, game: &Game) {
+        let mut tally = self.start_game(game);
+        self.add_events(&mut tally, game, game.events());
+    }
+
+    /// Count a new game, whose events are then added as they come with `add_events`
+    pub fn start_game(&mut self, game: &Game) -> GameTally {
+        self.games += 1;
+        GameTally {
+            jail: game
+                .board
+                .iter()
+                .position(|sq| sq.kind() == SquareKind::Jail)
+                .unwrap_or_default(),
+            positions: HashMap::new(),
+            houses: vec![0; game.board.len()],
+        }
+    }
+
+    pub fn add_events(&mut self, tally: &mut GameTally, game: &Game, events: &[Event]) {
+        for event in events {
+            match *event {
+                Event::Moved { player, to, .. } => {
+                    tally.positions.insert(player, to);
+                    self.squares[to].landings += 1;
+                }
+                Event::Jailed { player } => {
+                    tally.positions.insert(player, tally.jail);
+                    self.squares[tally.jail].landings += 1;
+                }
+                Event::Rent { player, amount, .. } => {
+                    if let Some(&position) = tally.positions.get(&player) {
+                        self.squares[position].rent += amount;
+                    }
+                }
+                Event::Bought {
+                    position, price, ..
+                } => self.squares[position].invested += price,
+                Event::Built { position, .. } => {
+                    let (house, hotel) = game.board[position].building_costs();
+                    tally.houses[position] += 1;
+                    self.squares[position].invested += if tally.houses[position] > 4 {
+                        hotel
+                    } else {
+                        house
+                    };
+                }
+                Event::SoldBuilding { position, .. } => {
+                    tally.houses[position] = tally.houses[position].saturating_sub(1);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// One value per square
+    pub fn metric(&self, metric: Metric) -> Vec<f64> {
+        let landings: usize = self.squares.iter().map(|s| s.landings).sum();
+        self.squares
+            .iter()
+            .map(|s| match metric {
+                Metric::Landings => s.landings as f64 / landings.max(1) as f64,
+                Metric::Rent => s.rent as f64 / self.games.max(1) as f64,
+                Metric::Roi => s.roi(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{locations::BoardLocation, scenario::Scenario};
+
+    use super::{BoardStats, Metric};
+
+    #[test]
+    fn landings_and_rent_are_counted_per_square() {
+        // Whitechapel Road with 2 houses charges 60
+        let game = Scenario::new(2)
+            .owner(1, 2)
+            .owner(3, 2)
+            .houses(3, 2)
+            .roll(&[1, 2])
+            .run()
+            .unwrap();
+
+        let mut stats = BoardStats::new(game.board.len());
+        stats.add_game(&game);
+        let whitechapel = BoardLocation::WhitechapelRoad.position();
+        assert_eq!(stats.squares[whitechapel].landings, 1);
+        assert_eq!(stats.squares[whitechapel].rent, 60);
+        assert_eq!(stats.metric(Metric::Landings)[whitechapel], 1.0);
+        assert_eq!(Metric::Landings.format(0.034), "3.4%");
+
+        // Adding the events a few at a time comes to the same
+        let mut piecemeal = BoardStats::new(game.board.len());
+        let mut tally = piecemeal.start_game(&game);
+        for events in game.events().chunks(2) {
+            piecemeal.add_events(&mut tally, &game, events);
+        }
+        assert_eq!(piecemeal, stats);
+    }
+}
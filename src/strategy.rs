@@ -0,0 +1,274 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    action::{Action, Decision, PendingDecision, TradeOffer},
+    agent::Agent,
+    locations::ColourGroup,
+    mcts::{MctsAgent, MctsConfig},
+    player::{PlayerId, JAIL_BAIL},
+    view::GameView,
+};
+
+const CAUTIOUS_RESERVE: usize = 300;
+const BID_STEP: usize = 10;
+
+/// Built-in opponents that can be picked by name in a config
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub enum Strategy {
+    Random,
+    Greedy,
+    Cautious,
+    Mcts,
+    Tuned(StrategyParams),
+}
+
+impl Strategy {
+    pub fn agent(self, seed: u64) -> Box<dyn Agent> {
+        match self {
+            Self::Random => Box::new(RandomAgent::new(seed)),
+            Self::Greedy => Box::new(HeuristicAgent::new(0)),
+            Self::Cautious => Box::new(HeuristicAgent::new(CAUTIOUS_RESERVE)),
+            Self::Mcts => Box::new(MctsAgent::new(MctsConfig::default(), seed)),
+            Self::Tuned(params) => Box::new(HeuristicAgent::with_params(params)),
+        }
+    }
+}
+
+/// Picks uniformly from whatever the engine allows
+#[derive(Debug, Clone)]
+pub struct RandomAgent {
+    rng: StdRng,
+}
+
+impl RandomAgent {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Agent for RandomAgent {
+    fn decide(&mut self, view: &GameView<'_>, _pending: &PendingDecision) -> Action {
+        view.legal_actions()
+            .choose(&mut self.rng)
+            .cloned()
+            .unwrap_or(Action::EndTurn)
+    }
+}
+
+/// The knobs of `HeuristicAgent`
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+pub struct StrategyParams {
+    /// Cash kept back when buying, bidding, building and trading
+    pub reserve: usize,
+    /// Most it will pay for a square in each colour group, in `ColourGroup::ALL` order
+    pub max_price: [usize; ColourGroup::ALL.len()],
+    /// Share of the spare cash it is prepared to put into a single building, 0 to 1
+    pub build_aggressiveness: f64,
+    /// Accepts trades bringing in this multiple of what goes out, and offers the same premium
+    pub trade_threshold: f64,
+}
+
+impl Default for StrategyParams {
+    fn default() -> Self {
+        Self {
+            reserve: 0,
+            max_price: [usize::MAX; ColourGroup::ALL.len()],
+            build_aggressiveness: 1.0,
+            trade_threshold: 1.5,
+        }
+    }
+}
+
+/// Buys, bids and builds whenever it can within the limits set by its `StrategyParams`
+#[derive(Debug, Clone)]
+pub struct HeuristicAgent {
+    params: StrategyParams,
+}
+
+impl HeuristicAgent {
+    pub fn new(reserve: usize) -> Self {
+        Self::with_params(StrategyParams {
+            reserve,
+            ..StrategyParams::default()
+        })
+    }
+
+    pub const fn with_params(params: StrategyParams) -> Self {
+        Self { params }
+    }
+
+    fn spare_cash(&self, view: &GameView<'_>, id: PlayerId) -> usize {
+        view.player(id)
+            .map_or(0, |p| p.cash.saturating_sub(self.params.reserve))
+    }
+
+    /// Most it will pay for the square, which is never more than the listed price
+    fn price_limit(&self, view: &GameView<'_>, position: usize) -> usize {
+        let sq = view.square(position);
+        let limit = sq
+            .group
+            .map_or(usize::MAX, |group| self.params.max_price[group.index()]);
+        sq.price.min(limit)
+    }
+
+    fn manage(&self, view: &GameView<'_>, id: PlayerId) -> Action {
+        let spare = self.spare_cash(view, id);
+        let budget = spare as f64 * self.params.build_aggressiveness;
+        let affordable = |action: &Action| match *action {
+            Action::Unmortgage(position) => view.square(position).unmortgage_cost < spare,
+            Action::Build(position) => (view.square(position).upgrade_cost as f64) < budget,
+            _ => false,
+        };
+
+        let legal = view.legal_actions();
+        if let Some(action) = legal.iter().find(|a| affordable(a)) {
+            return action.clone();
+        }
+
+        match self.set_completing_offer(view, id) {
+            Some(offer) if view.can_trade() => Action::ProposeTrade(offer),
+            _ => Action::EndTurn,
+        }
+    }
+
+    /// Offer a premium on the price for the last street of a colour group
+    fn set_completing_offer(&self, view: &GameView<'_>, id: PlayerId) -> Option<TradeOffer> {
+        let spare = self.spare_cash(view, id);
+        view.squares().find_map(|sq| {
+            let group = sq.group.filter(|g| g.is_buildable())?;
+            let owner = sq.owner.filter(|&owner| owner != id)?;
+            let missing = view
+                .squares()
+                .filter(|s| s.group == Some(group))
+                .filter(|s| s.owner != Some(id))
+                .count();
+            let price = (sq.price as f64 * self.params.trade_threshold) as usize;
+
+            if missing == 1 && !sq.has_buildings() && price < spare {
+                Some(TradeOffer {
+                    from: id,
+                    to: owner,
+                    give: vec![],
+                    take: vec![sq.position],
+                    give_cash: price,
+                    take_cash: 0,
+                })
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Accept when what comes in is worth at least `trade_threshold` times what goes out
+    fn accepts(&self, view: &GameView<'_>, offer: &TradeOffer) -> bool {
+        let value = |positions: &[usize]| -> usize {
+            positions.iter().map(|&p| view.square(p).price).sum()
+        };
+        let incoming = value(&offer.give) + offer.give_cash;
+        let outgoing = value(&offer.take) + offer.take_cash;
+
+        incoming as f64 >= outgoing as f64 * self.params.trade_threshold
+    }
+
+    /// After a bus roll, the move that lands on the least rent, going as far as possible
+    fn bus_stop(view: &GameView<'_>, id: PlayerId, faces: &[usize]) -> Action {
+        let total = faces.iter().sum();
+        let Ok(from) = view.player(id).map(|p| p.position) else {
+            return Action::MoveBy(total);
+        };
+        let rent = |steps: usize| {
+            let sq = view.square((from + steps) % view.board_size());
+            match sq.owner {
+                Some(owner) if owner != id && !sq.mortgaged => sq.rent,
+                _ => 0,
+            }
+        };
+        let steps = faces.iter().fold(total, |best, &steps| {
+            if rent(steps) < rent(best) {
+                steps
+            } else {
+                best
+            }
+        });
+        Action::MoveBy(steps)
+    }
+}
+
+impl Agent for HeuristicAgent {
+    fn decide(&mut self, view: &GameView<'_>, pending: &PendingDecision) -> Action {
+        let id = pending.player;
+        let spare = self.spare_cash(view, id);
+
+        match &pending.decision {
+            Decision::PreRoll => Action::Roll,
+            Decision::Jail => {
+                if view.player(id).is_ok_and(|p| p.jail_card) {
+                    Action::UseJailCard
+                } else if spare > JAIL_BAIL {
+                    Action::PayBail
+                } else {
+                    Action::RollForDoubles
+                }
+            }
+            Decision::Buy { position, price } => {
+                if spare > *price && *price <= self.price_limit(view, *position) {
+                    Action::Buy
+                } else {
+                    Action::Decline
+                }
+            }
+            Decision::Bid { position, highest } => {
+                let bid = highest + BID_STEP;
+                if bid <= self.price_limit(view, *position) && spare > bid {
+                    Action::Bid(bid)
+                } else {
+                    Action::Pass
+                }
+            }
+            Decision::IncomeTax { flat, percentage } => {
+                if percentage < flat {
+                    Action::PayTaxPercentage
+                } else {
+                    Action::PayFlatTax
+                }
+            }
+            Decision::Bus { faces } => Self::bus_stop(view, id, faces),
+            Decision::Manage => self.manage(view, id),
+            Decision::TradeResponse { offer } => {
+                if self.accepts(view, offer) {
+                    Action::AcceptTrade
+                } else {
+                    Action::RejectTrade
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::Game;
+
+    use super::Strategy;
+
+    #[test]
+    fn built_in_strategies_finish_games() {
+        let strategies = [Strategy::Random, Strategy::Greedy, Strategy::Cautious];
+        let mut agents: Vec<_> = strategies
+            .iter()
+            .enumerate()
+            .map(|(seed, s)| s.agent(seed as u64))
+            .collect();
+
+        let mut game = Game::new(strategies.len(), 5);
+        game.play(&mut agents, 500);
+
+        assert!(game.is_over() || game.rounds() == 500);
+        for player in game.players.iter().filter(|p| !p.is_active()) {
+            assert_eq!(game.net_worth(player.id).unwrap(), 0);
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use std::fmt::Write;
+
+use crate::{
+    config::BoardConfig,
+    game::Game,
+    locations::ColourGroup,
+    replay::{ring_cell, ring_side},
+    square::BoardPosition,
+    stats::Metric,
+};
+
+/// Width and height of one square on the ring
+const CELL: usize = 72;
+/// Colour band across the top of a street
+const BAND: usize = 12;
+const MARGIN: usize = 10;
+
+pub const PLAYER_COLOURS: [&str; 8] = [
+    "#e53935", "#43a047", "#fdd835", "#1e88e5", "#8e24aa", "#00acc1", "#fb8c00", "#6d4c41",
+];
+
+pub const fn group_colour(group: ColourGroup) -> &'static str {
+    match group {
+        ColourGroup::Brown => "#8b4513",
+        ColourGroup::LightBlue => "#aae0fa",
+        ColourGroup::Pink => "#d93a96",
+        ColourGroup::Orange => "#f7941d",
+        ColourGroup::Red => "#ed1b24",
+        ColourGroup::Yellow => "#fef200",
+        ColourGroup::Green => "#1fb25a",
+        ColourGroup::DarkBlue => "#0072bb",
+        ColourGroup::Station => "#404040",
+        ColourGroup::Utility => "#a0a0a0",
+    }
+}
+
+/// The game as it stands: owners' colours round each square, houses and hotels,
+/// mortgages and where every token is, with the players' cash in the middle
+pub fn snapshot(game: &Game) -> String {
+    let view = game.view();
+    let n = view.board_size();
+    let mut svg = open(n);
+    // Every id drawn comes from the game itself
+    let seat = |id| game.seat(id).unwrap_or_default();
+    let colour = |id| PLAYER_COLOURS[seat(id) % PLAYER_COLOURS.len()];
+
+    for sq in view.squares() {
+        let (x, y) = origin(sq.position, n);
+        let (stroke, width) = sq.owner.map_or(("#999", 1), |id| (colour(id), 3));
+        let _ = write!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="white" stroke="{}" stroke-width="{}"/>"#,
+            x, y, CELL, CELL, stroke, width
+        );
+        band(&mut svg, x, y, sq.group);
+        name(&mut svg, x, y, sq.name);
+
+        let row = y + CELL - 24;
+        if sq.hotels > 0 {
+            let _ = write!(
+                svg,
+                r##"<rect class="hotel" x="{}" y="{}" width="20" height="9" fill="#c62828"/>"##,
+                x + CELL / 2 - 10,
+                row
+            );
+        }
+        for house in 0..sq.houses {
+            let _ = write!(
+                svg,
+                r##"<rect class="house" x="{}" y="{}" width="9" height="9" fill="#2e7d32"/>"##,
+                x + 12 + house * 13,
+                row
+            );
+        }
+        if sq.mortgaged {
+            let _ = write!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="black" fill-opacity="0.25"/><text x="{}" y="{}" font-size="10" font-weight="bold" fill="#c62828" text-anchor="end">M</text>"##,
+                x,
+                y,
+                CELL,
+                CELL,
+                x + CELL - 3,
+                y + CELL - 3
+            );
+        }
+
+        let tokens = view
+            .players()
+            .filter(|p| p.active && p.position == sq.position);
+        for (i, player) in tokens.enumerate() {
+            let (cx, cy) = (x + 9 + i * 14, y + CELL - 8);
+            let _ = write!(
+                svg,
+                r#"<circle class="token" cx="{}" cy="{}" r="6" fill="{}" stroke="black"/><text x="{}" y="{}" font-size="8" text-anchor="middle" fill="white">{}</text>"#,
+                cx,
+                cy,
+                colour(player.id),
+                cx,
+                cy + 3,
+                seat(player.id) + 1
+            );
+        }
+    }
+
+    let (x, mut y) = centre(n);
+    heading(&mut svg, x, y, &format!("Round {}", view.rounds()));
+    for standing in view.leaderboard() {
+        y += 20;
+        let _ = write!(
+            svg,
+            r#"<rect x="{}" y="{}" width="12" height="12" fill="{}"/><text x="{}" y="{}" font-size="12">{} {} cash {} net worth {}</text>"#,
+            x,
+            y - 10,
+            colour(standing.player),
+            x + 18,
+            y,
+            seat(standing.player) + 1,
+            escape(&standing.name),
+            standing.cash,
+            if standing.active {
+                standing.net_worth.to_string()
+            } else {
+                "bankrupt".to_string()
+            }
+        );
+    }
+
+    svg + "</svg>\n"
+}
+
+/// One value per square of `board` shaded from white up to red at the highest
+pub fn heatmap(board: &BoardConfig, values: &[f64], metric: Metric) -> String {
+    let n = values.len();
+    let mut svg = open(n);
+    let max = values.iter().cloned().fold(0.0, f64::max);
+
+    for (position, &value) in values.iter().enumerate() {
+        let (x, y) = origin(position, n);
+        let square = board.squares.get(position);
+        let _ = write!(
+            svg,
+            r##"<rect class="heat" x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="#999"/>"##,
+            x,
+            y,
+            CELL,
+            CELL,
+            shade(if max > 0.0 { value / max } else { 0.0 })
+        );
+        band(&mut svg, x, y, square.and_then(|sq| sq.group));
+        name(
+            &mut svg,
+            x,
+            y,
+            &square.map_or(format!("Square {}", position), |sq| sq.name.clone()),
+        );
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="11" font-weight="bold" text-anchor="middle">{}</text>"#,
+            x + CELL / 2,
+            y + CELL - 8,
+            metric.format(value)
+        );
+    }
+
+    let (x, y) = centre(n);
+    heading(&mut svg, x, y, metric.title());
+    for step in 0..=10 {
+        let _ = write!(
+            svg,
+            r#"<rect x="{}" y="{}" width="20" height="14" fill="{}"/>"#,
+            x + step * 20,
+            y + 14,
+            shade(step as f64 / 10.0)
+        );
+    }
+    let _ = write!(
+        svg,
+        r#"<text x="{}" y="{}" font-size="10">{}</text><text x="{}" y="{}" font-size="10" text-anchor="end">{}</text>"#,
+        x,
+        y + 42,
+        metric.format(0.0),
+        x + 220,
+        y + 42,
+        metric.format(max)
+    );
+
+    svg + "</svg>\n"
+}
+
+fn open(board_size: usize) -> String {
+    let size = (ring_side(board_size) + 1) * CELL + 2 * MARGIN;
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}" font-family="sans-serif"><rect width="{0}" height="{0}" fill="#cde6d0"/>"##,
+        size
+    )
+}
+
+/// Top left corner of a square
+fn origin(position: BoardPosition, board_size: usize) -> (usize, usize) {
+    let (col, row) = ring_cell(position, board_size);
+    (MARGIN + col * CELL, MARGIN + row * CELL)
+}
+
+/// Top left of the space inside the ring
+fn centre(board_size: usize) -> (usize, usize) {
+    (
+        MARGIN + CELL + 20,
+        MARGIN + CELL + 40 + ring_side(board_size) * CELL / 4,
+    )
+}
+
+fn heading(svg: &mut String, x: usize, y: usize, text: &str) {
+    let _ = write!(
+        svg,
+        r#"<text x="{}" y="{}" font-size="20" font-weight="bold">{}</text>"#,
+        x,
+        y,
+        escape(text)
+    );
+}
+
+fn band(svg: &mut String, x: usize, y: usize, group: Option<ColourGroup>) {
+    if let Some(group) = group {
+        let _ = write!(
+            svg,
+            r##"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="#999"/>"##,
+            x,
+            y,
+            CELL,
+            BAND,
+            group_colour(group)
+        );
+    }
+}
+
+/// The name wrapped onto up to three lines under the colour band
+fn name(svg: &mut String, x: usize, y: usize, name: &str) {
+    let mut lines: Vec<String> = vec![];
+    for word in name.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.len() + word.len() < 12 => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    for (i, line) in lines.iter().take(3).enumerate() {
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="8" text-anchor="middle">{}</text>"#,
+            x + CELL / 2,
+            y + BAND + 10 + i * 9,
+            escape(line)
+        );
+    }
+}
+
+/// White at 0 through to deep red at 1
+fn shade(t: f64) -> String {
+    let t = t.clamp(0.0, 1.0);
+    let mix = |from: f64, to: f64| (from + (to - from) * t).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        mix(255.0, 198.0),
+        mix(255.0, 40.0),
+        mix(255.0, 40.0)
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{config::BoardConfig, scenario::Scenario, stats::Metric};
+
+    use super::{heatmap, shade, snapshot};
+
+    #[test]
+    fn snapshot_shows_owners_buildings_and_tokens() {
+        let game = Scenario::new(3)
+            .owner(1, 2)
+            .owner(3, 2)
+            .houses(3, 2)
+            .owner(39, 1)
+            .mortgaged(39)
+            .position(3, 39)
+            .build()
+            .unwrap();
+
+        let svg = snapshot(&game);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches(r#"class="token""#).count(), 3);
+        assert_eq!(svg.matches(r#"class="house""#).count(), 2);
+        assert!(svg.contains(">Mayfair<"));
+        assert!(svg.contains(">M</text>"));
+    }
+
+    #[test]
+    fn heatmap_shades_by_value() {
+        let mut values = vec![0.0; 40];
+        values[24] = 0.05;
+        values[39] = 0.025;
+        let svg = heatmap(&BoardConfig::us(), &values, Metric::Landings);
+        assert_eq!(svg.matches(r#"class="heat""#).count(), 40);
+        assert!(svg.contains("Illinois"));
+        assert!(svg.contains(&format!(r#"fill="{}""#, shade(1.0))));
+        assert!(svg.contains(">2.5%<"));
+        assert_eq!(shade(0.0), "#ffffff");
+    }
+}
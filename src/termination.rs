@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+/// When the game stops, on top of the last player standing which always ends it
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(default)]
+pub struct EndConditions {
+    /// Stop after this many rounds, the richest player by net worth winning
+    pub max_rounds: Option<usize>,
+    /// Stop as soon as anyone goes bankrupt
+    pub first_bankruptcy: bool,
+    /// Stop once no money has passed between players for this many rounds
+    pub stalemate_rounds: Option<usize>,
+}
+
+impl EndConditions {
+    /// Play on until one player is left
+    pub const fn last_standing() -> Self {
+        Self {
+            max_rounds: None,
+            first_bankruptcy: false,
+            stalemate_rounds: None,
+        }
+    }
+
+    pub const fn with_max_rounds(mut self, rounds: usize) -> Self {
+        self.max_rounds = Some(rounds);
+        self
+    }
+
+    pub const fn with_first_bankruptcy(mut self) -> Self {
+        self.first_bankruptcy = true;
+        self
+    }
+
+    pub const fn with_stalemate(mut self, rounds: usize) -> Self {
+        self.stalemate_rounds = Some(rounds);
+        self
+    }
+}
+
+/// Which of the end conditions finished the game
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum EndReason {
+    LastStanding,
+    MaxRounds,
+    FirstBankruptcy,
+    Stalemate,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        action::{Action, PendingDecision},
+        agent::Agent,
+        config::BoardConfig,
+        game::Game,
+        monopoly::MonopolyState,
+        player::Player,
+        rules::Rules,
+        strategy::Strategy,
+        view::GameView,
+    };
+
+    use super::{EndConditions, EndReason};
+
+    /// Never buys, bids or builds, so no rent is ever owed
+    struct Idle;
+
+    impl Agent for Idle {
+        fn decide(&mut self, view: &GameView<'_>, _pending: &PendingDecision) -> Action {
+            view.legal_actions().swap_remove(0)
+        }
+    }
+
+    fn play(end: EndConditions, seed: u64) -> Game {
+        let state = MonopolyState::seeded(seed).with_rules(Rules::default().with_end(end));
+        let mut game = Game::with_board(Player::create_players(4), &BoardConfig::uk(), state);
+        let mut agents: Vec<_> = (0..4).map(|s| Strategy::Greedy.agent(s)).collect();
+        game.play(&mut agents, 10_000);
+        game
+    }
+
+    #[test]
+    fn max_rounds_decide_on_net_worth() {
+        let game = play(EndConditions::last_standing().with_max_rounds(5), 1);
+        assert!(game.is_over());
+        assert_eq!(game.end_reason(), Some(EndReason::MaxRounds));
+        assert_eq!(game.rounds(), 5);
+        assert_eq!(game.winner(), Some(game.leaderboard()[0].player));
+    }
+
+    #[test]
+    fn first_bankruptcy_ends_the_game() {
+        for seed in 0..5 {
+            let game = play(EndConditions::last_standing().with_first_bankruptcy(), seed);
+            assert_eq!(game.end_reason(), Some(EndReason::FirstBankruptcy));
+            assert_eq!(game.eliminated().len(), 1);
+            let ranking = game.leaderboard();
+            assert_eq!(ranking[3].player, game.eliminated()[0]);
+        }
+    }
+
+    #[test]
+    fn last_standing_ranks_by_elimination() {
+        let game = play(EndConditions::last_standing(), 2);
+        assert_eq!(game.end_reason(), Some(EndReason::LastStanding));
+        let ranking: Vec<_> = game.leaderboard().iter().map(|s| s.player).collect();
+        let mut eliminated = game.eliminated().to_vec();
+        eliminated.reverse();
+        assert_eq!(ranking[0], game.winner().unwrap());
+        assert_eq!(ranking[1..], eliminated[..]);
+    }
+
+    #[test]
+    fn stalemate_when_no_money_changes_hands() {
+        let end = EndConditions::last_standing().with_stalemate(3);
+        let state = MonopolyState::seeded(0).with_rules(Rules::default().with_end(end));
+        let mut game = Game::with_board(Player::create_players(2), &BoardConfig::uk(), state);
+        let mut agents: Vec<Box<dyn Agent>> = vec![Box::new(Idle), Box::new(Idle)];
+        game.play(&mut agents, 100);
+        assert_eq!(game.end_reason(), Some(EndReason::Stalemate));
+        assert_eq!(game.rounds(), 3);
+    }
+}
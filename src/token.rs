@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 #[allow(dead_code)]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Token {
     Iron,
     Terrier,
@@ -14,6 +16,19 @@ pub enum Token {
 }
 
 impl Token {
+    pub const ALL: [Self; 10] = [
+        Self::Iron,
+        Self::Terrier,
+        Self::Horse,
+        Self::TopHat,
+        Self::Cannon,
+        Self::Wheelbarrow,
+        Self::Battleship,
+        Self::Thimble,
+        Self::Car,
+        Self::Boot,
+    ];
+
     pub const fn value_to_enum(value: usize) -> Self {
         match value {
             1 => Self::Iron,
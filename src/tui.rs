@@ -0,0 +1,420 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{self, Event as TermEvent, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Cell, Paragraph, Row, Table},
+    Frame as Screen,
+};
+
+use crate::{
+    agent::Agent,
+    game::Game,
+    locations::ColourGroup,
+    player::PlayerId,
+    replay::{ring_cell, ring_side, Frame, Replay},
+};
+
+const PLAYER_COLOURS: [Color; 8] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::LightRed,
+    Color::LightGreen,
+];
+
+const MIN_DELAY: Duration = Duration::from_millis(10);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+const fn group_colour(group: ColourGroup) -> Color {
+    match group {
+        ColourGroup::Brown => Color::Rgb(150, 90, 50),
+        ColourGroup::LightBlue => Color::LightCyan,
+        ColourGroup::Pink => Color::LightMagenta,
+        ColourGroup::Orange => Color::Rgb(255, 150, 0),
+        ColourGroup::Red => Color::Red,
+        ColourGroup::Yellow => Color::Yellow,
+        ColourGroup::Green => Color::Green,
+        ColourGroup::DarkBlue => Color::Blue,
+        ColourGroup::Station => Color::White,
+        ColourGroup::Utility => Color::Gray,
+    }
+}
+
+/// Where the turns come from
+enum Source {
+    Live {
+        game: Box<Game>,
+        agents: Vec<Box<dyn Agent>>,
+        max_rounds: usize,
+    },
+    Replay,
+}
+
+/// Full screen view of a game: the ring of squares, the players and an event feed.
+/// Runs on its own at the chosen speed or one turn at a time while paused.
+pub struct Viewer {
+    replay: Replay,
+    source: Source,
+    /// Index of the frame on screen
+    shown: usize,
+    paused: bool,
+    delay: Duration,
+}
+
+impl Viewer {
+    /// Watch the agents play `game`, recording it as it goes
+    pub fn live(mut game: Game, agents: Vec<Box<dyn Agent>>, max_rounds: usize) -> Self {
+        game.record_events(true);
+        Self::new(
+            Replay::new(&game),
+            Source::Live {
+                game: Box::new(game),
+                agents,
+                max_rounds,
+            },
+        )
+    }
+
+    pub fn replay(replay: Replay) -> Self {
+        Self::new(replay, Source::Replay)
+    }
+
+    const fn new(replay: Replay, source: Source) -> Self {
+        Self {
+            replay,
+            source,
+            shown: 0,
+            paused: false,
+            delay: Duration::from_millis(300),
+        }
+    }
+
+    /// Everything seen so far, to be saved once the viewer is closed
+    pub fn into_replay(self) -> Replay {
+        self.replay
+    }
+
+    /// Show the next turn, playing it first when live. False at the end of the game.
+    pub fn step(&mut self) -> bool {
+        if self.shown + 1 < self.replay.frames.len() {
+            self.shown += 1;
+            return true;
+        }
+
+        match &mut self.source {
+            Source::Live {
+                game,
+                agents,
+                max_rounds,
+            } if !game.is_over() && game.rounds() < *max_rounds => {
+                game.play_turn(agents);
+                self.replay.push(game);
+                self.shown += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn back(&mut self) {
+        self.shown = self.shown.saturating_sub(1);
+    }
+
+    /// Take over the terminal until `q` is pressed
+    pub fn run(&mut self) -> io::Result<()> {
+        let mut terminal = ratatui::init();
+        let result = self.event_loop(&mut terminal);
+        ratatui::restore();
+        result
+    }
+
+    fn event_loop(&mut self, terminal: &mut ratatui::DefaultTerminal) -> io::Result<()> {
+        let mut last_step = Instant::now();
+        loop {
+            terminal.draw(|screen| self.draw(screen))?;
+
+            let timeout = if self.paused {
+                Duration::from_millis(250)
+            } else {
+                self.delay.saturating_sub(last_step.elapsed())
+            };
+            if event::poll(timeout)? {
+                if let TermEvent::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                            KeyCode::Char(' ') => self.paused = !self.paused,
+                            KeyCode::Char('n') | KeyCode::Right => {
+                                self.paused = true;
+                                self.step();
+                            }
+                            KeyCode::Char('p') | KeyCode::Left => {
+                                self.paused = true;
+                                self.back();
+                            }
+                            KeyCode::Char('+') | KeyCode::Up => {
+                                self.delay = (self.delay / 2).max(MIN_DELAY);
+                            }
+                            KeyCode::Char('-') | KeyCode::Down => {
+                                self.delay = (self.delay * 2).min(MAX_DELAY);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            if !self.paused && last_step.elapsed() >= self.delay {
+                if !self.step() {
+                    self.paused = true;
+                }
+                last_step = Instant::now();
+            }
+        }
+    }
+
+    fn frame(&self) -> &Frame {
+        &self.replay.frames[self.shown]
+    }
+
+    fn player_colour(&self, id: PlayerId) -> Color {
+        let seat = self
+            .replay
+            .players
+            .iter()
+            .position(|p| p.id == id)
+            .unwrap_or_default();
+        PLAYER_COLOURS[seat % PLAYER_COLOURS.len()]
+    }
+
+    pub fn draw(&self, screen: &mut Screen<'_>) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(screen.area());
+        let [board, side] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(44)]).areas(main);
+        let [players, feed] = Layout::vertical([
+            Constraint::Length(self.replay.players.len() as u16 + 3),
+            Constraint::Min(0),
+        ])
+        .areas(side);
+
+        self.draw_board(screen, board);
+        self.draw_players(screen, players);
+        self.draw_feed(screen, feed);
+        self.draw_status(screen, status);
+    }
+
+    fn draw_board(&self, screen: &mut Screen<'_>, area: Rect) {
+        let n = self.replay.squares.len();
+        let across = ring_side(n) + 1;
+        let rows = Layout::vertical(vec![Constraint::Ratio(1, across as u32); across]).split(area);
+        let cell = |col: usize, row: usize| {
+            Layout::horizontal(vec![Constraint::Ratio(1, across as u32); across]).split(rows[row])
+                [col]
+        };
+
+        let frame = self.frame();
+        for (position, (sq, state)) in self.replay.squares.iter().zip(&frame.squares).enumerate() {
+            let (col, row) = ring_cell(position, n);
+            let border = state
+                .owner
+                .map_or(Style::default().fg(Color::DarkGray), |id| {
+                    Style::default().fg(self.player_colour(id))
+                });
+            let name = Span::styled(
+                sq.name.as_str(),
+                sq.group.map_or(Style::default(), |g| {
+                    Style::default()
+                        .fg(group_colour(g))
+                        .add_modifier(Modifier::BOLD)
+                }),
+            );
+            let mut buildings = vec![Span::styled(
+                match state.hotels {
+                    0 => "^".repeat(state.houses),
+                    _ => "H".to_string(),
+                },
+                Style::default().fg(Color::Green),
+            )];
+            if state.mortgaged {
+                buildings.push(Span::styled(" M", Style::default().fg(Color::Red)));
+            }
+            let tokens: Vec<Span<'_>> = frame
+                .players
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.active && p.position == position)
+                .map(|(seat, p)| {
+                    Span::styled(
+                        format!("{} ", seat + 1),
+                        Style::default()
+                            .fg(self.player_colour(p.id))
+                            .add_modifier(Modifier::REVERSED),
+                    )
+                })
+                .collect();
+
+            screen.render_widget(
+                Paragraph::new(vec![
+                    Line::from(name),
+                    Line::from(buildings),
+                    Line::from(tokens),
+                ])
+                .block(Block::bordered().border_style(border)),
+                cell(col, row),
+            );
+        }
+
+        let centre = Rect {
+            x: cell(1, 1).x,
+            y: cell(1, 1).y,
+            width: cell(across - 2, 1).right() - cell(1, 1).x,
+            height: cell(1, across - 2).bottom() - cell(1, 1).y,
+        };
+        let turn = self.replay.player_name(frame.turn);
+        screen.render_widget(
+            Paragraph::new(vec![
+                Line::from(format!("Round {}", frame.round)),
+                Line::from(format!("{} to play", turn)),
+                Line::from(format!("Free parking {}", frame.free_parking)),
+            ])
+            .centered(),
+            Rect {
+                y: centre.y + centre.height / 2,
+                height: 3.min(centre.height),
+                ..centre
+            },
+        );
+    }
+
+    fn draw_players(&self, screen: &mut Screen<'_>, area: Rect) {
+        let frame = self.frame();
+        let rows = frame.players.iter().enumerate().map(|(seat, p)| {
+            let status = if !p.active {
+                "bankrupt"
+            } else if p.in_jail {
+                "in jail"
+            } else {
+                ""
+            };
+            let marker = if p.id == frame.turn { ">" } else { " " };
+            Row::new(vec![
+                Cell::from(format!("{}{}", marker, seat + 1)),
+                Cell::from(self.replay.player_name(p.id).to_string()),
+                Cell::from(p.cash.to_string()),
+                Cell::from(p.net_worth.to_string()),
+                Cell::from(status),
+            ])
+            .style(Style::default().fg(self.player_colour(p.id)))
+        });
+
+        screen.render_widget(
+            Table::new(
+                rows,
+                [
+                    Constraint::Length(3),
+                    Constraint::Min(8),
+                    Constraint::Length(6),
+                    Constraint::Length(6),
+                    Constraint::Length(8),
+                ],
+            )
+            .header(Row::new(vec!["", "Player", "Cash", "Worth", ""]))
+            .block(Block::bordered().title("Players")),
+            area,
+        );
+    }
+
+    fn draw_feed(&self, screen: &mut Screen<'_>, area: Rect) {
+        let lines: Vec<Line<'_>> = self.replay.frames[..=self.shown]
+            .iter()
+            .flat_map(|f| &f.events)
+            .map(|e| Line::from(self.replay.describe(e)))
+            .collect();
+        // Keep the latest events in view
+        let height = area.height.saturating_sub(2) as usize;
+        let scroll = lines.len().saturating_sub(height) as u16;
+        screen.render_widget(
+            Paragraph::new(lines)
+                .scroll((scroll, 0))
+                .block(Block::bordered().title("Events")),
+            area,
+        );
+    }
+
+    fn draw_status(&self, screen: &mut Screen<'_>, area: Rect) {
+        let state = if self.paused { "Paused" } else { "Running" };
+        screen.render_widget(
+            Paragraph::new(format!(
+                " Turn {}/{} | {} | {}ms per turn | space pause  n/→ step  p/← back  +/- speed  q quit",
+                self.shown,
+                self.replay.frames.len() - 1,
+                state,
+                self.delay.as_millis()
+            ))
+            .style(Style::default().add_modifier(Modifier::REVERSED)),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use crate::{game::Game, replay::Replay, strategy::Strategy};
+
+    use super::Viewer;
+
+    fn screen(viewer: &Viewer) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(180, 50)).unwrap();
+        terminal.draw(|screen| viewer.draw(screen)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn live_games_are_played_turn_by_turn() {
+        let agents = (0..2).map(|s| Strategy::Greedy.agent(s)).collect();
+        let mut viewer = Viewer::live(Game::new(2, 5), agents, 10);
+        let text = screen(&viewer);
+        assert!(text.contains("Mayfair"));
+        assert!(text.contains("Player 1"));
+
+        assert!(viewer.step());
+        assert!(screen(&viewer).contains("Player 1 rolled"));
+        while viewer.step() {}
+        assert_eq!(viewer.into_replay().frames.last().unwrap().round, 10);
+    }
+
+    #[test]
+    fn replays_step_forward_and_back() {
+        let mut game = Game::new(2, 5);
+        let mut agents: Vec<_> = (0..2).map(|s| Strategy::Greedy.agent(s)).collect();
+        let replay = Replay::record(&mut game, &mut agents, 3);
+        let frames = replay.frames.len();
+
+        let mut viewer = Viewer::replay(replay);
+        let mut steps = 0;
+        while viewer.step() {
+            steps += 1;
+        }
+        assert_eq!(steps, frames - 1);
+        viewer.back();
+        assert_eq!(viewer.shown, frames - 2);
+    }
+}
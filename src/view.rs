@@ -0,0 +1,326 @@
+use std::ops::RangeInclusive;
+
+use serde::Serialize;
+
+use crate::{
+    action::{Action, PendingDecision},
+    error::MonopolyError,
+    event::Event,
+    game::Game,
+    locations::{ColourGroup, SquareKind},
+    observation::Snapshot,
+    phase::Phase,
+    player::{Player, PlayerId},
+    portfolio::{Portfolio, Standing},
+    square::{BoardPosition, BoardSquare},
+    termination::EndReason,
+    token::Token,
+};
+
+/// The most likely total of two dice, used to price utilities between rolls
+pub const TYPICAL_ROLL: usize = 7;
+
+/// What everyone at the table can see of a player
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerView<'a> {
+    pub id: PlayerId,
+    pub name: &'a str,
+    pub token: Token,
+    pub position: BoardPosition,
+    pub cash: usize,
+    pub active: bool,
+    /// Turns already spent in jail, if in jail
+    pub jail: Option<usize>,
+    pub jail_card: bool,
+}
+
+impl<'a> PlayerView<'a> {
+    fn new(player: &'a Player) -> Self {
+        let s = player.state.borrow();
+        Self {
+            id: player.id,
+            name: &player.name,
+            token: player.token,
+            position: s.current_position,
+            cash: s.balance,
+            active: s.active,
+            jail: s.jail,
+            jail_card: s.jail_card,
+        }
+    }
+
+    pub const fn in_jail(&self) -> bool {
+        self.jail.is_some()
+    }
+
+    /// Mirrors `Player::can_afford`
+    pub const fn can_afford(&self, charge: usize) -> bool {
+        self.cash >= charge
+    }
+}
+
+/// A square as printed on the board plus who owns it and what stands on it
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct SquareView<'a> {
+    pub position: BoardPosition,
+    pub name: &'a str,
+    pub kind: SquareKind,
+    pub group: Option<ColourGroup>,
+    pub price: usize,
+    pub owner: Option<PlayerId>,
+    pub houses: usize,
+    pub hotels: usize,
+    pub mortgaged: bool,
+    /// What landing on it costs right now, before any mortgage is taken into account.
+    /// Utilities are priced at a roll of `TYPICAL_ROLL`.
+    pub rent: usize,
+    /// Cost of the next house or hotel
+    pub upgrade_cost: usize,
+    pub mortgage_value: usize,
+    pub unmortgage_cost: usize,
+}
+
+impl<'a> SquareView<'a> {
+    fn new(sq: &'a BoardSquare, rent: usize) -> Self {
+        let (houses, hotels) = sq.buildings();
+        Self {
+            position: sq.position(),
+            name: sq.name(),
+            kind: sq.kind(),
+            group: sq.colour_group(),
+            price: sq.get_purchase_cost(),
+            owner: sq.owner_id(),
+            houses,
+            hotels,
+            mortgaged: sq.is_mortgaged(),
+            rent,
+            upgrade_cost: sq.upgrade_cost(),
+            mortgage_value: sq.mortgage_value(),
+            unmortgage_cost: sq.unmortgage_cost(),
+        }
+    }
+
+    pub const fn is_ownable(&self) -> bool {
+        self.kind.is_ownable()
+    }
+
+    pub const fn has_buildings(&self) -> bool {
+        self.houses + self.hotels > 0
+    }
+}
+
+/// Everything public about the table at once, e.g. for a front end to draw
+#[derive(Debug, Serialize)]
+pub struct TableState<'a> {
+    pub round: usize,
+    pub turn: PlayerId,
+    pub free_parking: usize,
+    pub players: Vec<PlayerView<'a>>,
+    pub squares: Vec<SquareView<'a>>,
+    pub leaderboard: Vec<Standing>,
+    pub winner: Option<PlayerId>,
+    pub end_reason: Option<EndReason>,
+}
+
+/// The game as an agent is allowed to see it. Everything here is public at the
+/// table and nothing can be changed through it. The order of the card piles,
+/// the dice still to come and the other agents stay hidden.
+#[derive(Debug, Clone, Copy)]
+pub struct GameView<'a> {
+    game: &'a Game,
+}
+
+impl<'a> GameView<'a> {
+    pub(crate) const fn new(game: &'a Game) -> Self {
+        Self { game }
+    }
+
+    pub fn board_size(&self) -> usize {
+        self.game.board.len()
+    }
+
+    pub fn square(&self, position: BoardPosition) -> SquareView<'a> {
+        SquareView::new(
+            &self.game.board[position],
+            self.game.rent(position, TYPICAL_ROLL),
+        )
+    }
+
+    pub fn squares(&self) -> impl Iterator<Item = SquareView<'a>> {
+        let game = self.game;
+        game.board
+            .iter()
+            .map(move |sq| SquareView::new(sq, game.rent(sq.position(), TYPICAL_ROLL)))
+    }
+
+    /// In turn order
+    pub fn players(&self) -> impl Iterator<Item = PlayerView<'a>> {
+        self.game.players.iter().map(PlayerView::new)
+    }
+
+    pub fn player(&self, id: PlayerId) -> Result<PlayerView<'a>, MonopolyError> {
+        Ok(PlayerView::new(self.game.player(id)?))
+    }
+
+    pub fn current_player(&self) -> PlayerId {
+        self.game.current_player()
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.game.rounds()
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.game.phase()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.game.is_over()
+    }
+
+    pub fn winner(&self) -> Option<PlayerId> {
+        self.game.winner()
+    }
+
+    pub fn end_reason(&self) -> Option<EndReason> {
+        self.game.end_reason()
+    }
+
+    pub fn eliminated(&self) -> &'a [PlayerId] {
+        self.game.eliminated()
+    }
+
+    pub fn free_parking(&self) -> usize {
+        self.game.state.free_parking.borrow().0
+    }
+
+    /// Empty unless `Game::record_events` is on
+    pub fn events(&self) -> &'a [Event] {
+        self.game.events()
+    }
+
+    pub fn pending(&self) -> Option<PendingDecision> {
+        self.game.pending()
+    }
+
+    pub fn legal_actions(&self) -> Vec<Action> {
+        self.game.legal_actions()
+    }
+
+    pub fn legal_actions_for(&self, id: PlayerId) -> Vec<Action> {
+        self.game.legal_actions_for(id)
+    }
+
+    pub fn is_legal(&self, action: &Action) -> bool {
+        self.game.is_legal(action)
+    }
+
+    pub fn bid_range(&self) -> Option<RangeInclusive<usize>> {
+        self.game.bid_range()
+    }
+
+    pub fn can_trade(&self) -> bool {
+        self.game.can_trade()
+    }
+
+    pub fn owns_colour_group(&self, id: PlayerId, group: ColourGroup) -> bool {
+        self.game.owns_colour_group(id, group)
+    }
+
+    pub fn portfolio(&self, id: PlayerId) -> Result<Portfolio, MonopolyError> {
+        self.game.portfolio(id)
+    }
+
+    pub fn net_worth(&self, id: PlayerId) -> Result<usize, MonopolyError> {
+        self.game.net_worth(id)
+    }
+
+    pub fn leaderboard(&self) -> Vec<Standing> {
+        self.game.leaderboard()
+    }
+
+    pub fn table_state(&self) -> TableState<'a> {
+        TableState {
+            round: self.rounds(),
+            turn: self.current_player(),
+            free_parking: self.free_parking(),
+            players: self.players().collect(),
+            squares: self.squares().collect(),
+            leaderboard: self.leaderboard(),
+            winner: self.winner(),
+            end_reason: self.end_reason(),
+        }
+    }
+
+    pub fn snapshot(&self, observer: PlayerId) -> Snapshot {
+        Snapshot::from_game(self.game, observer)
+    }
+
+    /// A copy of the game to search ahead on. The dice and cards still to come are
+    /// re-drawn from `seed`, so the copy gives nothing away about the real ones.
+    /// It keeps no event log, searching ahead has no use for one.
+    pub fn determinize(&self, seed: u64) -> Game {
+        let mut game = self.game.clone();
+        game.reseed(seed);
+        game.record_events(false);
+        game.take_events();
+        game
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{action::Action, game::Game, scenario::Scenario};
+
+    #[test]
+    fn view_shows_the_table() {
+        let game = Scenario::new(2)
+            .cash(2, 900)
+            .owner(1, 2)
+            .houses(1, 2)
+            .jail_card(1)
+            .build()
+            .unwrap();
+        let view = game.view();
+
+        let player = view.player(2).unwrap();
+        assert_eq!(
+            (player.cash, player.position, player.active),
+            (900, 0, true)
+        );
+        assert!(view.player(1).unwrap().jail_card);
+        assert_eq!(view.players().count(), 2);
+
+        let square = view.square(1);
+        assert_eq!(square.owner, Some(2));
+        assert_eq!((square.houses, square.hotels), (2, 0));
+        assert_eq!(view.squares().filter(|s| s.has_buildings()).count(), 1);
+        assert_eq!(view.legal_actions(), game.legal_actions());
+
+        let state = serde_json::to_value(view.table_state()).unwrap();
+        assert_eq!(state["players"][1]["cash"], 900);
+        assert_eq!(state["squares"][1]["houses"], 2);
+        assert_eq!(state["leaderboard"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn determinized_copies_are_detached() {
+        let game = Game::new(3, 2);
+        let copy = game.view().determinize(9);
+        copy.player(1).unwrap().deposit(1000);
+        assert_eq!(game.player(1).unwrap().current_balance(), 1500);
+
+        // The scripted roll of 3 is only known to the real game
+        let game = Scenario::new(2).roll(&[1, 2]).build().unwrap();
+        let moved_to = |seed| {
+            let mut copy = game.view().determinize(seed);
+            copy.apply(Action::Roll).unwrap();
+            copy.player(1).unwrap().current_position()
+        };
+        assert!((0..20).any(|seed| moved_to(seed) != 3));
+
+        let mut copy = game.view().determinize(9);
+        copy.apply(Action::Roll).unwrap();
+        assert!(copy.events().is_empty());
+    }
+}
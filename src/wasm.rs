@@ -0,0 +1,123 @@
+use std::fmt::Display;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    agent::Agent,
+    batch::{run_batch, BatchConfig},
+    config::BoardConfig,
+    game::Game,
+    player::PlayerId,
+    roster::{PlayerConfig, Roster},
+    strategy::Strategy,
+    termination::EndConditions,
+};
+
+fn js_error(e: impl Display) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// A JSON list such as `["Greedy", {"Tuned": {...}}]`
+fn strategies(json: &str) -> Result<Vec<Strategy>, JsError> {
+    serde_json::from_str(json).map_err(js_error)
+}
+
+/// A preset name or a board as JSON
+fn board_config(board: &str) -> Result<BoardConfig, JsError> {
+    match BoardConfig::preset(board) {
+        Some(board) => Ok(board),
+        None => BoardConfig::from_json(board).map_err(js_error),
+    }
+}
+
+/// A game for a page to step through, state going back and forth as JSON
+#[wasm_bindgen]
+pub struct WasmGame {
+    game: Game,
+    agents: Vec<Box<dyn Agent>>,
+    max_rounds: usize,
+}
+
+#[wasm_bindgen]
+impl WasmGame {
+    /// `board` is a preset name such as `uk`, or a whole board as JSON
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        strategies_json: &str,
+        board: &str,
+        seed: u32,
+        max_rounds: usize,
+    ) -> Result<WasmGame, JsError> {
+        let board = board_config(board)?;
+        let players = strategies(strategies_json)?
+            .into_iter()
+            .enumerate()
+            .map(|(seat, s)| PlayerConfig::new(&format!("Player {}", seat + 1), s))
+            .collect();
+        let (mut game, agents) = Roster::new(players)
+            .game(&board, seed.into())
+            .map_err(js_error)?;
+        game.state.rules.end = EndConditions::last_standing().with_max_rounds(max_rounds);
+        game.record_events(true);
+
+        Ok(Self {
+            game,
+            agents,
+            max_rounds,
+        })
+    }
+
+    /// Play one turn, false once the game is over
+    pub fn step(&mut self) -> bool {
+        self.game.rounds() < self.max_rounds && self.game.play_turn(&mut self.agents)
+    }
+
+    /// Play to the end and return the winner
+    pub fn play(&mut self) -> Option<PlayerId> {
+        self.game.play(&mut self.agents, self.max_rounds);
+        self.game.winner()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rounds(&self) -> usize {
+        self.game.rounds()
+    }
+
+    #[wasm_bindgen(getter, js_name = isOver)]
+    pub fn is_over(&self) -> bool {
+        self.game.is_over()
+    }
+
+    /// `TableState` as JSON
+    pub fn state(&self) -> Result<String, JsError> {
+        serde_json::to_string(&self.game.view().table_state()).map_err(js_error)
+    }
+
+    /// Events from index `from` on, so a page can keep a feed without re-reading it all
+    pub fn events(&self, from: usize) -> Result<String, JsError> {
+        let events = self.game.events().get(from..).unwrap_or_default();
+        serde_json::to_string(events).map_err(js_error)
+    }
+}
+
+/// `run_batch` between the strategies, with the `BatchResult` as JSON
+#[wasm_bindgen]
+pub fn batch(
+    strategies_json: &str,
+    board: &str,
+    games: usize,
+    max_rounds: usize,
+    seed: u32,
+) -> Result<String, JsError> {
+    let strategies = strategies(strategies_json)?;
+    let board = board_config(board)?;
+    let config = BatchConfig {
+        games,
+        max_rounds,
+        seed: seed.into(),
+    };
+    let result = run_batch(&board, strategies.len(), config, |e, seed| {
+        strategies[e].agent(seed)
+    });
+    serde_json::to_string(&result).map_err(js_error)
+}